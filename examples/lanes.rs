@@ -0,0 +1,19 @@
+use micro_ndarray::Array;
+
+fn main() {
+    let mut array = Array::new_with([5, 4], 0);
+    // the same as examples/iterators.rs, but only visits the column that changes
+    for (y, x) in array.axis_iter_mut::<1>(0).nth(1).unwrap().iter_mut() {
+        *x += y[0];
+    }
+    for row in array.lanes(0) {
+        for (_, x) in row {
+            print!("{x}");
+        }
+        println!();
+    }
+    assert_eq!(
+        array.iter().map(|x| *x.1).collect::<Vec<_>>(),
+        vec![0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 2, 0, 0, 0, 0, 3, 0, 0, 0]
+    )
+}
@@ -1,14 +1,19 @@
-use std::{
-    ops::{Index, IndexMut},
-    slice,
-};
+use std::ops::{Index, IndexMut};
 
-use crate::iterator::Iter;
+use crate::{
+    error::Error,
+    iterator::{Cursor, Indices, Iter, IterMut},
+    slice::Slice,
+    view::{permute_axes, ArrayView, ArrayViewMut},
+};
 
 #[cfg(not(feature = "allocator"))]
 mod no_alloc {
     use std::marker::PhantomData;
 
+    use super::{allocation_stride, size_panic, standard_stride};
+    use crate::Error;
+
     pub trait Allocator {}
     #[derive(Clone, Copy)]
     pub struct Global;
@@ -22,36 +27,52 @@ mod no_alloc {
         phantom_a: PhantomData<A>,
     }
 
+    impl<T, const D: usize, A: Allocator> Array<T, D, A> {
+        pub(crate) fn from_parts(size: [usize; D], stride: [usize; D], data: Vec<T>) -> Self {
+            Self {
+                size,
+                stride,
+                data,
+                phantom_a: PhantomData,
+            }
+        }
+    }
+
     impl<T: Default + Clone, const D: usize> Array<T, D> {
         pub fn new(size: [usize; D]) -> Self {
             Self::new_with(size, T::default())
         }
+
+        /// Like [`new`](Self::new), but returns an error if the size overflows.
+        pub fn try_new(size: [usize; D]) -> Result<Self, Error> {
+            Self::try_new_with(size, T::default())
+        }
     }
 
     impl<T: Clone, const D: usize> Array<T, D> {
         pub fn new_with(size: [usize; D], item: T) -> Self {
-            let mut l = 1;
-            let mut stride = [0usize; D];
-            for (i, dim) in size.into_iter().enumerate() {
-                stride[i] = l;
-                l *= dim;
-            }
-            Self {
+            Self::try_new_with(size, item).unwrap_or_else(|e| size_panic(size, e))
+        }
+
+        /// Like [`new_with`](Self::new_with), but returns an error if the size overflows.
+        pub fn try_new_with(size: [usize; D], item: T) -> Result<Self, Error> {
+            let (stride, l) = allocation_stride::<T, D>(size)?;
+            Ok(Self {
                 size,
                 stride,
                 data: vec![item; l],
                 phantom_a: PhantomData,
-            }
+            })
         }
     }
     impl<T, const D: usize> Array<T, D> {
         pub fn new_by<F: Fn() -> T>(size: [usize; D], supplier: F) -> Self {
-            let mut l = 1;
-            let mut stride = [0usize; D];
-            for (i, dim) in size.into_iter().enumerate() {
-                stride[i] = l;
-                l *= dim;
-            }
+            Self::try_new_by(size, supplier).unwrap_or_else(|e| size_panic(size, e))
+        }
+
+        /// Like [`new_by`](Self::new_by), but returns an error if the size overflows.
+        pub fn try_new_by<F: Fn() -> T>(size: [usize; D], supplier: F) -> Result<Self, Error> {
+            let (stride, l) = allocation_stride::<T, D>(size)?;
             let mut r = Self {
                 size,
                 stride,
@@ -61,16 +82,20 @@ mod no_alloc {
             for _ in 0..l {
                 r.data.push(supplier());
             }
-            r
+            Ok(r)
         }
 
         pub fn new_by_enumeration<F: Fn(usize) -> T>(size: [usize; D], supplier: F) -> Self {
-            let mut l = 1;
-            let mut stride = [0usize; D];
-            for (i, dim) in size.into_iter().enumerate() {
-                stride[i] = l;
-                l *= dim;
-            }
+            Self::try_new_by_enumeration(size, supplier).unwrap_or_else(|e| size_panic(size, e))
+        }
+
+        /// Like [`new_by_enumeration`](Self::new_by_enumeration), but returns an error if the
+        /// size overflows.
+        pub fn try_new_by_enumeration<F: Fn(usize) -> T>(
+            size: [usize; D],
+            supplier: F,
+        ) -> Result<Self, Error> {
+            let (stride, l) = allocation_stride::<T, D>(size)?;
             let mut r = Self {
                 size,
                 stride,
@@ -80,26 +105,31 @@ mod no_alloc {
             for i in 0..l {
                 r.data.push(supplier(i));
             }
-            r
+            Ok(r)
         }
 
-        /// Flattens the ND Array into a 1D Array with indexing `x + y * size_x + z * size_x * size_y` etc. This is a zero-cost operation.
-        pub fn into_flattened(self) -> Vec<T> {
+        /// Flattens the ND Array into a 1D Array with indexing `x + y * size_x + z * size_x * size_y` etc. This is a zero-cost operation
+        /// unless the axes have been permuted, in which case the elements are reordered first.
+        pub fn into_flattened(mut self) -> Vec<T> {
+            self.make_standard_layout();
             self.data
         }
 
         /// Reinterprets a 1D array as an ND Array with indexing `x + y * size_x + z * size_x * size_y` etc. This is a zero-cost operation.
         pub fn from_flat(array: Vec<T>, size: [usize; D]) -> Option<Self> {
-            let mut l = 1;
-            let mut stride = [0usize; D];
-            for (i, dim) in size.into_iter().enumerate() {
-                stride[i] = l;
-                l *= dim;
-            }
+            Self::try_from_flat(array, size).ok()
+        }
+
+        /// Like [`from_flat`](Self::from_flat), but returns why the shape was rejected.
+        pub fn try_from_flat(array: Vec<T>, size: [usize; D]) -> Result<Self, Error> {
+            let (stride, l) = standard_stride(size)?;
             if l != array.len() {
-                return None;
+                return Err(Error::ShapeMismatch {
+                    expected: l,
+                    got: array.len(),
+                });
             }
-            Some(Self {
+            Ok(Self {
                 data: array,
                 size,
                 stride,
@@ -125,9 +155,9 @@ mod alloc {
                 // args
                 ( $($arg_name:ident : $arg_type:ty),*; $alloc:ident: A )
                 // return type
-                -> $ret:tt
+                -> $ret:ty
             // code
-            $block:tt
+            $block:block
         ) => {
             // implements for ANY allocator
             impl<T: $($t + )*, const D: usize, A: Allocator> Array<T, D, A> {
@@ -154,6 +184,9 @@ mod alloc {
     pub use std::alloc::Allocator;
     use std::{alloc::Global, vec};
 
+    use super::{allocation_stride, size_panic, standard_stride};
+    use crate::Error;
+
     #[derive(Clone)]
     pub struct Array<T, const D: usize, A: Allocator = Global> {
         pub(crate) size: [usize; D],
@@ -165,27 +198,29 @@ mod alloc {
         Self::new_with_in(size, T::default(), alloc)
     });
 
+    insert_functions!(Clone, Default: pub fn try_new(size: [usize; D]; alloc: A) -> Result<Self, Error> {
+        Self::try_new_with_in(size, T::default(), alloc)
+    });
+
     insert_functions!(Clone: pub fn new_with(size: [usize; D], item: T; alloc: A) -> Self {
-        let mut l = 1;
-        let mut stride = [0usize; D];
-        for (i, dim) in size.into_iter().enumerate() {
-            stride[i] = l;
-            l *= dim;
-        }
-        Self {
+        Self::try_new_with_in(size, item, alloc).unwrap_or_else(|e| size_panic(size, e))
+    });
+
+    insert_functions!(Clone: pub fn try_new_with(size: [usize; D], item: T; alloc: A) -> Result<Self, Error> {
+        let (stride, l) = allocation_stride::<T, D>(size)?;
+        Ok(Self {
             size,
             stride,
             data: vec::from_elem_in(item, l, alloc),
-        }
+        })
     });
 
     insert_functions!(: pub fn new_by<F: (Fn() -> T)>(size: [usize; D], supplier: F; alloc: A) -> Self {
-        let mut l = 1;
-        let mut stride = [0usize; D];
-        for (i, dim) in size.into_iter().enumerate() {
-            stride[i] = l;
-            l *= dim;
-        }
+        Self::try_new_by_in(size, supplier, alloc).unwrap_or_else(|e| size_panic(size, e))
+    });
+
+    insert_functions!(: pub fn try_new_by<F: (Fn() -> T)>(size: [usize; D], supplier: F; alloc: A) -> Result<Self, Error> {
+        let (stride, l) = allocation_stride::<T, D>(size)?;
         let mut r = Self {
             size,
             stride,
@@ -194,16 +229,15 @@ mod alloc {
         for _ in 0..l {
             r.data.push(supplier());
         }
-        r
+        Ok(r)
     });
 
     insert_functions!(: pub fn new_by_enumeration<F: (Fn(usize) -> T)>(size: [usize; D], supplier: F; alloc: A) -> Self {
-        let mut l = 1;
-        let mut stride = [0usize; D];
-        for (i, dim) in size.into_iter().enumerate() {
-            stride[i] = l;
-            l *= dim;
-        }
+        Self::try_new_by_enumeration_in(size, supplier, alloc).unwrap_or_else(|e| size_panic(size, e))
+    });
+
+    insert_functions!(: pub fn try_new_by_enumeration<F: (Fn(usize) -> T)>(size: [usize; D], supplier: F; alloc: A) -> Result<Self, Error> {
+        let (stride, l) = allocation_stride::<T, D>(size)?;
         let mut r = Self {
             size,
             stride,
@@ -212,27 +246,36 @@ mod alloc {
         for i in 0..l {
             r.data.push(supplier(i));
         }
-        r
+        Ok(r)
     });
 
-    impl<'a, T, const D: usize, A: Allocator> Array<T, D, A> {
-        /// Flattens the ND Array into a 1D Array with indexing `x + y * size_x + z * size_x * size_y` etc. This is a zero-cost operation.
-        pub fn into_flattened(self) -> Vec<T, A> {
+    impl<T, const D: usize, A: Allocator> Array<T, D, A> {
+        pub(crate) fn from_parts(size: [usize; D], stride: [usize; D], data: Vec<T, A>) -> Self {
+            Self { size, stride, data }
+        }
+
+        /// Flattens the ND Array into a 1D Array with indexing `x + y * size_x + z * size_x * size_y` etc. This is a zero-cost operation
+        /// unless the axes have been permuted, in which case the elements are reordered first.
+        pub fn into_flattened(mut self) -> Vec<T, A> {
+            self.make_standard_layout();
             self.data
         }
 
         /// Reinterprets a 1D array as an ND Array with indexing `x + y * size_x + z * size_x * size_y` etc. This is a zero-cost operation.
         pub fn from_flat(array: Vec<T, A>, size: [usize; D]) -> Option<Self> {
-            let mut l = 1;
-            let mut stride = [0usize; D];
-            for (i, dim) in size.into_iter().enumerate() {
-                stride[i] = l;
-                l *= dim;
-            }
+            Self::try_from_flat(array, size).ok()
+        }
+
+        /// Like [`from_flat`](Self::from_flat), but returns why the shape was rejected.
+        pub fn try_from_flat(array: Vec<T, A>, size: [usize; D]) -> Result<Self, Error> {
+            let (stride, l) = standard_stride(size)?;
             if l != array.len() {
-                return None;
+                return Err(Error::ShapeMismatch {
+                    expected: l,
+                    got: array.len(),
+                });
             }
-            Some(Self {
+            Ok(Self {
                 data: array,
                 size,
                 stride,
@@ -245,141 +288,426 @@ pub use alloc::*;
 
 // "Allocator" in all future uses refers to the re-exported or the placeholder.
 
+/// Computes the strides of the standard layout along with the number of elements, or
+/// [`Error::SizeOverflow`] if the number of elements does not fit into a usize.
+pub(crate) fn standard_stride<const D: usize>(
+    size: [usize; D],
+) -> Result<([usize; D], usize), Error> {
+    let mut l: usize = 1;
+    let mut stride = [0usize; D];
+    for (i, dim) in size.into_iter().enumerate() {
+        stride[i] = l;
+        l = l.checked_mul(dim).ok_or(Error::SizeOverflow)?;
+    }
+    Ok((stride, l))
+}
+
+/// Like [`standard_stride`], but also checks that the elements fit into memory, so allocating
+/// them can not panic with a capacity overflow.
+pub(crate) fn allocation_stride<T, const D: usize>(
+    size: [usize; D],
+) -> Result<([usize; D], usize), Error> {
+    let (stride, l) = standard_stride(size)?;
+    l.checked_mul(size_of::<T>())
+        .filter(|&bytes| bytes <= isize::MAX as usize)
+        .ok_or(Error::SizeOverflow)?;
+    Ok((stride, l))
+}
+
+pub(crate) fn size_panic<const D: usize>(size: [usize; D], e: Error) -> ! {
+    panic!("Can not create an array of size {size:?}: {e}")
+}
+
 impl<'a, T, const D: usize, A: Allocator> Array<T, D, A> {
     pub fn size(&self) -> [usize; D] {
         self.size
     }
 
+    /// The distance in elements between two neighbours along each dimension.
+    pub fn stride(&self) -> [usize; D] {
+        self.stride
+    }
+
     pub fn get(&'a self, loc: [usize; D]) -> Option<&'a T> {
-        self.internal_get(loc, false)
+        self.try_get(loc).ok()
+    }
+
+    /// Like [`get`](Self::get), but returns which axis was out of bounds on failure.
+    pub fn try_get(&'a self, loc: [usize; D]) -> Result<&'a T, Error> {
+        let real_loc = self.internal_offset(loc)?;
+        unsafe {
+            // SAFETY this is checked by internal_offset
+            Ok(self.data.get_unchecked(real_loc))
+        }
     }
 
     #[inline]
-    fn internal_get(&'a self, loc: [usize; D], panic: bool) -> Option<&'a T> {
-        let mut real_loc = 0;
-        for (i, &(mut dim)) in loc.iter().enumerate() {
+    fn internal_offset(&self, loc: [usize; D]) -> Result<usize, Error> {
+        for (i, &dim) in loc.iter().enumerate() {
             if dim >= self.size[i] {
-                if panic {
-                    panic!(
-                        "Array index of dimension {} is out of bounds! 0..{}.contains({}) == false",
-                        i + 1,
-                        self.size[i],
-                        dim
-                    )
-                } else {
-                    return None;
-                }
-            }
-            if i == 0 {
-                real_loc += dim;
-                continue;
+                return Err(Error::OutOfBounds {
+                    axis: i,
+                    index: dim,
+                    len: self.size[i],
+                });
             }
-            dim *= self.stride[i];
-            real_loc += dim;
-        }
-        unsafe {
-            // SAFETY this is checked in the previous lines
-            Some(self.data.get_unchecked(real_loc))
         }
+        Ok(self.flat_index_unchecked(loc))
     }
 
-    pub unsafe fn get_unchecked(&'a self, loc: [usize; D]) -> &'a T {
+    /// Position of `loc` in [`as_flattened`](Self::as_flattened), without checking bounds.
+    #[inline]
+    pub(crate) fn flat_index_unchecked(&self, loc: [usize; D]) -> usize {
         let mut real_loc = 0;
-        for (i, &(mut dim)) in loc.iter().enumerate() {
-            if i == 0 {
-                real_loc += dim;
-                continue;
-            }
-            dim *= self.stride[i];
-            real_loc += dim;
+        for (i, dim) in loc.iter().enumerate() {
+            real_loc += *dim * self.stride[i];
         }
-        self.data.get_unchecked(real_loc)
+        real_loc
+    }
+
+    /// # Safety
+    /// `loc` must be within the size of the array.
+    pub unsafe fn get_unchecked(&'a self, loc: [usize; D]) -> &'a T {
+        self.data.get_unchecked(self.flat_index_unchecked(loc))
     }
 
     pub fn get_mut(&'a mut self, loc: [usize; D]) -> Option<&'a mut T> {
-        self.internal_get_mut(loc, false)
+        self.try_get_mut(loc).ok()
     }
 
-    #[inline]
-    fn internal_get_mut(&'a mut self, loc: [usize; D], panic: bool) -> Option<&'a mut T> {
-        let mut real_loc = 0;
-        for (i, &(mut dim)) in loc.iter().enumerate() {
-            if dim >= self.size[i] {
-                if panic {
-                    panic!(
-                        "Array index of dimension {} is out of bounds! 0..{}.contains({}) == false",
-                        i + 1,
-                        self.size[i],
-                        dim
-                    )
-                } else {
-                    return None;
-                }
-            }
-            if i == 0 {
-                real_loc += dim;
-                continue;
+    /// Like [`get_mut`](Self::get_mut), but returns which axis was out of bounds on failure.
+    pub fn try_get_mut(&'a mut self, loc: [usize; D]) -> Result<&'a mut T, Error> {
+        let real_loc = self.internal_offset(loc)?;
+        unsafe {
+            // SAFETY this is checked by internal_offset
+            Ok(self.data.get_unchecked_mut(real_loc))
+        }
+    }
+
+    /// # Safety
+    /// `loc` must be within the size of the array.
+    pub unsafe fn get_unchecked_mut(&'a mut self, loc: [usize; D]) -> &'a mut T {
+        let real_loc = self.flat_index_unchecked(loc);
+        self.data.get_unchecked_mut(real_loc)
+    }
+
+    /// Returns the position of `loc` in [`as_flattened`](Self::as_flattened), or `None` if it
+    /// is out of bounds.
+    pub fn flat_index(&self, loc: [usize; D]) -> Option<usize> {
+        self.internal_offset(loc).ok()
+    }
+
+    /// Returns the location of the element at `index` in [`as_flattened`](Self::as_flattened),
+    /// or `None` if it is out of bounds. This is the inverse of [`flat_index`](Self::flat_index)
+    /// and works for any order of the axes in memory.
+    pub fn unflat_index(&self, mut index: usize) -> Option<[usize; D]> {
+        if index >= self.data.len() {
+            return None;
+        }
+        let mut axes = [0; D];
+        for (i, axis) in axes.iter_mut().enumerate() {
+            *axis = i;
+        }
+        // axes of length 1 may share their stride with another axis, so they go last
+        axes.sort_unstable_by_key(|&axis| (self.size[axis] != 1, self.stride[axis]));
+        let mut loc = [0; D];
+        for &axis in axes.iter().rev() {
+            if self.size[axis] != 1 {
+                loc[axis] = index / self.stride[axis];
+                index %= self.stride[axis];
             }
-            dim *= self.stride[i];
-            real_loc += dim;
         }
+        Some(loc)
+    }
+
+    /// Iterates over the locations of all elements in x-first order, without borrowing the
+    /// array.
+    pub fn indices(&self) -> Indices<D> {
+        Indices::new(self.size)
+    }
+
+    pub fn iter(&self) -> Iter<'_, T, D> {
+        self.view().iter()
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T, D> {
+        self.view_mut().into_iter()
+    }
+
+    /// Borrows the whole array as an [`ArrayView`].
+    pub fn view(&self) -> ArrayView<'_, T, D> {
+        // SAFETY size and stride describe exactly the elements of data
         unsafe {
-            // SAFETY this is checked in the previous lines
-            Some(self.data.get_unchecked_mut(real_loc))
+            ArrayView::from_raw_parts(
+                self.data.as_ptr(),
+                self.size,
+                self.stride.map(|x| x as isize),
+            )
         }
     }
 
-    pub unsafe fn get_unchecked_mut(&'a mut self, loc: [usize; D]) -> &'a mut T {
-        let mut real_loc = 0;
-        for (i, &(mut dim)) in loc.iter().enumerate() {
-            if i == 0 {
-                real_loc += dim;
-                continue;
+    /// Mutably borrows the whole array as an [`ArrayViewMut`].
+    pub fn view_mut(&mut self) -> ArrayViewMut<'_, T, D> {
+        // SAFETY size and stride describe exactly the elements of data
+        unsafe {
+            ArrayViewMut::from_raw_parts(
+                self.data.as_mut_ptr(),
+                self.size,
+                self.stride.map(|x| x as isize),
+            )
+        }
+    }
+
+    /// Stretches the array to `size` without copying, see [`ArrayView::broadcast_to`].
+    pub fn broadcast_to(&self, size: [usize; D]) -> Result<ArrayView<'_, T, D>, Error> {
+        self.view().broadcast_to(size)
+    }
+
+    /// Returns a view of the part of the array selected by `info`, see [`s!`](crate::s).
+    pub fn slice(&self, info: [Slice; D]) -> ArrayView<'_, T, D> {
+        self.view().slice(info)
+    }
+
+    /// Like [`slice`](Self::slice), but returns an error instead of panicking.
+    pub fn try_slice(&self, info: [Slice; D]) -> Result<ArrayView<'_, T, D>, Error> {
+        self.view().try_slice(info)
+    }
+
+    /// Returns a mutable view of the part of the array selected by `info`, see
+    /// [`s!`](crate::s).
+    pub fn slice_mut(&mut self, info: [Slice; D]) -> ArrayViewMut<'_, T, D> {
+        self.view_mut().into_slice(info)
+    }
+
+    /// Like [`slice_mut`](Self::slice_mut), but returns an error instead of panicking.
+    pub fn try_slice_mut(&mut self, info: [Slice; D]) -> Result<ArrayViewMut<'_, T, D>, Error> {
+        self.view_mut().try_into_slice(info)
+    }
+
+    /// Swaps two axes of the array. This is a zero-cost operation that only swaps their sizes
+    /// and strides, which means the array will not be in standard layout afterwards.
+    pub fn swap_axes(&mut self, a: usize, b: usize) {
+        self.size.swap(a, b);
+        self.stride.swap(a, b);
+    }
+
+    /// Reorders the axes of the array so that axis `i` of the result is axis `axes[i]` of
+    /// `self`. Like [`swap_axes`](Self::swap_axes), this only rewrites sizes and strides.
+    /// Panics if `axes` is not a permutation of `0..D`.
+    pub fn permuted_axes(mut self, axes: [usize; D]) -> Self {
+        (self.size, self.stride) = permute_axes(self.size, self.stride, axes);
+        self
+    }
+
+    /// Returns true if the elements are stored with indexing
+    /// `x + y * size_x + z * size_x * size_y` etc., which is the case unless the axes have been
+    /// permuted.
+    pub fn is_standard_layout(&self) -> bool {
+        Ok((self.stride, self.data.len())) == standard_stride(self.size)
+    }
+
+    /// Reorders the elements in memory so that the array is in standard layout again, see
+    /// [`is_standard_layout`](Self::is_standard_layout).
+    pub fn make_standard_layout(&mut self) {
+        if self.is_standard_layout() {
+            return;
+        }
+        // sources[i] is the memory location of the element that belongs at i
+        let sources: Vec<usize> = self
+            .view()
+            .iter()
+            .map(|(loc, _)| {
+                loc.iter()
+                    .zip(self.stride)
+                    .map(|(dim, stride)| dim * stride)
+                    .sum()
+            })
+            .collect();
+        let mut visited = vec![false; sources.len()];
+        for start in 0..sources.len() {
+            let mut i = start;
+            while !visited[i] {
+                visited[i] = true;
+                if sources[i] == start {
+                    break;
+                }
+                self.data.swap(i, sources[i]);
+                i = sources[i];
             }
-            dim *= self.stride[i];
-            real_loc += dim;
         }
-        self.data.get_unchecked_mut(real_loc)
+        self.stride = standard_stride(self.size).unwrap().0;
     }
 
-    pub fn iter(&self) -> Iter<slice::Iter<T>, D> {
-        Iter::new(self)
+    /// Changes the size and dimensionality of the array while keeping the elements in standard
+    /// layout order. This is a zero-cost operation unless the axes have been permuted, in which
+    /// case the elements are reordered first.
+    pub fn reshape<const E: usize>(mut self, size: [usize; E]) -> Result<Array<T, E, A>, Error> {
+        let (stride, l) = standard_stride(size)?;
+        if l != self.data.len() {
+            return Err(Error::ShapeMismatch {
+                expected: l,
+                got: self.data.len(),
+            });
+        }
+        self.make_standard_layout();
+        Ok(Array::from_parts(size, stride, self.data))
     }
 
-    pub fn iter_mut(&mut self) -> Iter<slice::IterMut<T>, D> {
-        Iter::new_mut(self)
+    /// Inserts an axis of length 1 before `axis`, increasing the dimensionality by one. This is a
+    /// zero-cost operation. `E` must be `D + 1`.
+    pub fn insert_axis<const E: usize>(self, axis: usize) -> Array<T, E, A> {
+        const {
+            assert!(
+                E == D + 1,
+                "insert_axis must increase the dimensionality by one"
+            )
+        };
+        assert!(
+            axis <= D,
+            "Axis {axis} is out of bounds for insertion into {D} axes"
+        );
+        let mut size = [1; E];
+        let mut stride = [0; E];
+        for i in 0..E {
+            if i < axis {
+                size[i] = self.size[i];
+                stride[i] = self.stride[i];
+            } else if i > axis {
+                size[i] = self.size[i - 1];
+                stride[i] = self.stride[i - 1];
+            } else if i < D {
+                // keep the array in standard layout if it is
+                stride[i] = self.stride[i];
+            } else if i > 0 {
+                stride[i] = self.stride[i - 1] * self.size[i - 1];
+            } else {
+                stride[i] = 1;
+            }
+        }
+        Array::from_parts(size, stride, self.data)
     }
 
-    /// Flattens the ND Array into a 1D Array with indexing `x + y * size_x + z * size_x * size_y` etc. This is a zero-cost operation.
+    /// Removes an axis of length 1, decreasing the dimensionality by one. This is a zero-cost
+    /// operation. `E` must be `D - 1`. Panics if the axis does not have length 1.
+    pub fn remove_axis<const E: usize>(self, axis: usize) -> Array<T, E, A> {
+        const {
+            assert!(
+                E + 1 == D,
+                "remove_axis must decrease the dimensionality by one"
+            )
+        };
+        assert!(axis < D, "Axis {axis} is out of bounds for {D} axes");
+        assert_eq!(self.size[axis], 1, "Only axes of length 1 can be removed");
+        let mut size = [0; E];
+        let mut stride = [0; E];
+        for i in 0..E {
+            let j = if i < axis { i } else { i + 1 };
+            size[i] = self.size[j];
+            stride[i] = self.stride[j];
+        }
+        Array::from_parts(size, stride, self.data)
+    }
+
+    /// Flattens the ND Array into a 1D Array in memory order. This is a zero-cost operation and
+    /// uses indexing `x + y * size_x + z * size_x * size_y` etc. if the array
+    /// [`is_standard_layout`](Self::is_standard_layout).
     pub fn as_flattened(&self) -> &[T] {
         self.data.as_slice()
     }
 
-    /// Flattens the ND Array into a 1D Array with indexing `x + y * size_x + z * size_x * size_y` etc. This is a zero-cost operation.
+    /// Flattens the ND Array into a 1D Array in memory order. This is a zero-cost operation and
+    /// uses indexing `x + y * size_x + z * size_x * size_y` etc. if the array
+    /// [`is_standard_layout`](Self::is_standard_layout).
     pub fn as_flattened_mut(&mut self) -> &mut [T] {
         self.data.as_mut_slice()
     }
 }
 
+impl<T: Clone, const D: usize, A: Allocator> Array<T, D, A> {
+    /// Changes the size of the array without changing its dimensionality. Elements keep their
+    /// location if it is still within the array, and new locations are filled with `fill`.
+    pub fn resize(&mut self, size: [usize; D], fill: T) {
+        self.try_resize(size, fill)
+            .unwrap_or_else(|e| size_panic(size, e))
+    }
+
+    /// Like [`resize`](Self::resize), but returns an error if the size overflows. The array is
+    /// not changed in that case.
+    pub fn try_resize(&mut self, size: [usize; D], fill: T) -> Result<(), Error> {
+        let (stride, l) = allocation_stride::<T, D>(size)?;
+        self.make_standard_layout();
+        let old_size = self.size;
+        let old_stride = self.stride;
+        // the array is empty until the new elements are in place, so a panicking clone can not
+        // leave it with fewer elements than its size
+        self.size = [0; D];
+        let mut old = self.data.drain(..).map(Some).collect::<Vec<_>>();
+        self.data.reserve_exact(l);
+        for (loc, _) in Cursor::new(size, [0; D]) {
+            if loc
+                .iter()
+                .zip(old_size)
+                .all(|(&dim, old_dim)| dim < old_dim)
+            {
+                let real_loc: usize = loc
+                    .iter()
+                    .zip(old_stride)
+                    .map(|(dim, stride)| dim * stride)
+                    .sum();
+                // this unwrap can not panic as every old location is visited at most once
+                self.data.push(old[real_loc].take().unwrap());
+            } else {
+                self.data.push(fill.clone());
+            }
+        }
+        self.size = size;
+        self.stride = stride;
+        Ok(())
+    }
+}
+
+impl<T, A: Allocator> Array<T, 2, A> {
+    /// Swaps the two axes of a 2D array without moving any elements.
+    pub fn transpose(self) -> Self {
+        self.permuted_axes([1, 0])
+    }
+}
+
 impl<T, const D: usize> Index<[usize; D]> for Array<T, D> {
     type Output = T;
 
     fn index(&self, index: [usize; D]) -> &Self::Output {
-        // SAFETY this unwrap can not panic due to panic:true in the args of internal_get
-        self.internal_get(index, true).unwrap()
+        self.try_get(index).unwrap_or_else(|e| panic!("{e}"))
     }
 }
 
 impl<T, const D: usize> IndexMut<[usize; D]> for Array<T, D> {
     fn index_mut(&mut self, index: [usize; D]) -> &mut T {
-        // SAFETY this unwrap can not panic due to panic:true in the args of internal_get_mut
-        self.internal_get_mut(index, true).unwrap()
+        self.try_get_mut(index).unwrap_or_else(|e| panic!("{e}"))
+    }
+}
+
+impl<'a, T, const D: usize, A: Allocator> IntoIterator for &'a Array<T, D, A> {
+    type Item = ([usize; D], &'a T);
+    type IntoIter = Iter<'a, T, D>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T, const D: usize, A: Allocator> IntoIterator for &'a mut Array<T, D, A> {
+    type Item = ([usize; D], &'a mut T);
+    type IntoIter = IterMut<'a, T, D>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
     }
 }
 
 #[cfg(test)]
 mod test {
-    use crate::Array;
+    use crate::{s, Array, Error};
 
     #[test]
     fn iterator() {
@@ -402,4 +730,152 @@ mod test {
             vec![0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 2, 0, 0, 0, 0, 3, 0, 0, 0, 0]
         )
     }
+
+    #[test]
+    fn permuted_axes() {
+        let mut array = Array::new_by_enumeration([3, 2, 2], |i| i);
+        array.swap_axes(0, 2);
+        assert_eq!(array.size(), [2, 2, 3]);
+        assert_eq!(array[[1, 0, 2]], 8);
+        assert!(!array.is_standard_layout());
+        let collected = array.iter().map(|x| *x.1).collect::<Vec<_>>();
+        assert_eq!(collected[..4], [0, 6, 3, 9]);
+        assert_eq!(array.clone().into_flattened(), collected);
+        array.make_standard_layout();
+        assert!(array.is_standard_layout());
+        assert_eq!(array.as_flattened(), collected);
+        assert_eq!(array[[1, 0, 2]], 8);
+
+        let array = Array::new_by_enumeration([3, 2], |i| i).transpose();
+        assert_eq!(array.size(), [2, 3]);
+        assert_eq!(array[[1, 2]], 5);
+        assert_eq!(array.view().transpose()[[2, 1]], 5);
+    }
+
+    #[test]
+    fn reshape() {
+        let array = Array::new_by_enumeration([3, 4], |i| i);
+        let reshaped = array.clone().reshape([2, 3, 2]).unwrap();
+        assert_eq!(reshaped[[1, 2, 1]], 11);
+        assert!(array.clone().reshape([5]).is_err());
+        // permuted arrays are reordered first
+        let transposed = array.clone().transpose().reshape([12]).unwrap();
+        assert_eq!(transposed[[1]], 3);
+
+        let inserted = array.insert_axis::<3>(1);
+        assert_eq!(inserted.size(), [3, 1, 4]);
+        assert!(inserted.is_standard_layout());
+        assert_eq!(inserted[[2, 0, 1]], 5);
+        let removed = inserted.remove_axis::<2>(1);
+        assert_eq!(removed.size(), [3, 4]);
+        assert_eq!(removed[[2, 1]], 5);
+    }
+
+    #[test]
+    fn resize() {
+        let mut array = Array::new_by_enumeration([3, 2], |i| i);
+        array.resize([2, 3], 9);
+        assert_eq!(array.as_flattened(), &[0, 1, 3, 4, 9, 9]);
+        array.resize([1, 1], 9);
+        assert_eq!(array.as_flattened(), &[0]);
+        assert_eq!(
+            array.try_resize([usize::MAX, 2], 9),
+            Err(Error::SizeOverflow)
+        );
+        assert_eq!(
+            array.try_resize([usize::MAX / 8, 2], 9),
+            Err(Error::SizeOverflow)
+        );
+        assert_eq!(array.as_flattened(), &[0]);
+
+        #[derive(Debug)]
+        struct PanicOnClone;
+        impl Clone for PanicOnClone {
+            fn clone(&self) -> Self {
+                panic!("clone")
+            }
+        }
+        let mut array = Array::new_by([1, 1], || PanicOnClone);
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            array.resize([3, 2], PanicOnClone)
+        }));
+        assert!(result.is_err());
+        assert!(array.get([0, 0]).is_none());
+    }
+
+    #[test]
+    fn flat_indices() {
+        let mut array = Array::new_by_enumeration([3, 1, 4], |i| i);
+        for loc in array.indices() {
+            let index = array.flat_index(loc).unwrap();
+            assert_eq!(array.as_flattened()[index], array[loc]);
+            assert_eq!(array.unflat_index(index), Some(loc));
+        }
+        assert_eq!(array.flat_index([3, 0, 0]), None);
+        assert_eq!(array.unflat_index(12), None);
+
+        array = array.permuted_axes([2, 1, 0]);
+        assert_eq!(array.indices().len(), 12);
+        for (loc, x) in array.indices().zip(array.iter()) {
+            assert_eq!(loc, x.0);
+            assert_eq!(array.unflat_index(*x.1), Some(loc));
+        }
+    }
+
+    #[test]
+    fn errors() {
+        let array = Array::new_with([3, 2], 0);
+        assert_eq!(
+            array.try_get([1, 2]),
+            Err(Error::OutOfBounds {
+                axis: 1,
+                index: 2,
+                len: 2
+            })
+        );
+        assert_eq!(
+            Array::try_from_flat(vec![0; 5], [3, 2]).err(),
+            Some(Error::ShapeMismatch {
+                expected: 6,
+                got: 5
+            })
+        );
+        assert_eq!(
+            array.try_slice(s![..;0, ..]).err(),
+            Some(Error::ZeroStep { axis: 0 })
+        );
+    }
+
+    #[test]
+    fn size_overflow() {
+        let size = [usize::MAX / 2, 3];
+        assert_eq!(
+            Array::<u8, 2>::try_new(size).err(),
+            Some(Error::SizeOverflow)
+        );
+        assert_eq!(
+            Array::try_new_by_enumeration(size, |i| i).err(),
+            Some(Error::SizeOverflow)
+        );
+        assert_eq!(
+            Array::try_from_flat(vec![0u8; 0], [usize::MAX, 2, 0]).err(),
+            Some(Error::SizeOverflow)
+        );
+        // the number of elements fits into a usize, but not their bytes
+        assert_eq!(
+            Array::<u64, 1>::try_new([usize::MAX / 4]).err(),
+            Some(Error::SizeOverflow)
+        );
+        assert_eq!(
+            Array::try_new_by([usize::MAX / 4], || 0u64).err(),
+            Some(Error::SizeOverflow)
+        );
+        assert!(Array::<(), 1>::try_new([usize::MAX]).is_ok());
+    }
+
+    #[test]
+    #[should_panic(expected = "overflows")]
+    fn size_overflow_panic() {
+        Array::new_with([usize::MAX, usize::MAX], 0u8);
+    }
 }
@@ -0,0 +1,154 @@
+use crate::{array::Allocator, Array, ArrayView, ArrayViewMut, Error, Slice};
+
+impl<T, const D: usize> ArrayViewMut<'_, T, D> {
+    /// Sets every element of the view to `value`.
+    pub fn fill(&mut self, value: T)
+    where
+        T: Clone,
+    {
+        self.iter_mut().for_each(|(_, x)| *x = value.clone());
+    }
+
+    /// Copies the elements of `other` into the view. `other` is broadcast to the size of the
+    /// view first (see [`ArrayView::broadcast_to`]), so a single row can be assigned to every
+    /// row of the view. Panics if that is not possible.
+    pub fn assign(&mut self, other: ArrayView<'_, T, D>)
+    where
+        T: Clone,
+    {
+        self.try_assign(other).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Like [`assign`](Self::assign), but returns an error instead of panicking.
+    pub fn try_assign(&mut self, other: ArrayView<'_, T, D>) -> Result<(), Error>
+    where
+        T: Clone,
+    {
+        let other = other.broadcast_to(self.size)?;
+        self.iter_mut()
+            .zip(other.iter())
+            .for_each(|((_, x), (_, y))| *x = y.clone());
+        Ok(())
+    }
+
+    /// Copies all of `src` into the view, placing its first element at `dest_origin`. Returns
+    /// an error if `src` does not fit into the view at that location, in which case nothing is
+    /// copied.
+    pub fn copy_region(
+        &mut self,
+        src: ArrayView<'_, T, D>,
+        dest_origin: [usize; D],
+    ) -> Result<(), Error>
+    where
+        T: Clone,
+    {
+        let mut info = [Slice::from(..); D];
+        for i in 0..D {
+            let end = dest_origin[i]
+                .checked_add(src.size[i])
+                .ok_or(Error::OutOfBounds {
+                    axis: i,
+                    index: dest_origin[i],
+                    len: self.size[i],
+                })?;
+            info[i] = Slice::from(dest_origin[i]..end);
+        }
+        self.try_slice_mut(info)?.try_assign(src)
+    }
+}
+
+impl<T, const D: usize, A: Allocator> Array<T, D, A> {
+    /// Sets every element of the array to `value`.
+    pub fn fill(&mut self, value: T)
+    where
+        T: Clone,
+    {
+        self.data.fill(value);
+    }
+
+    /// Copies the elements of `other` into the array, see [`ArrayViewMut::assign`].
+    pub fn assign(&mut self, other: ArrayView<'_, T, D>)
+    where
+        T: Clone,
+    {
+        self.view_mut().assign(other)
+    }
+
+    /// Like [`assign`](Self::assign), but returns an error instead of panicking.
+    pub fn try_assign(&mut self, other: ArrayView<'_, T, D>) -> Result<(), Error>
+    where
+        T: Clone,
+    {
+        self.view_mut().try_assign(other)
+    }
+
+    /// Copies all of `src` into the array at `dest_origin`, see
+    /// [`ArrayViewMut::copy_region`].
+    pub fn copy_region(
+        &mut self,
+        src: ArrayView<'_, T, D>,
+        dest_origin: [usize; D],
+    ) -> Result<(), Error>
+    where
+        T: Clone,
+    {
+        self.view_mut().copy_region(src, dest_origin)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{s, Array, Error};
+
+    #[test]
+    fn fill_and_assign() {
+        let mut array = Array::new_with([3, 2], 0);
+        array.fill(7);
+        assert_eq!(array.as_flattened(), &[7; 6]);
+        array.slice_mut(s![1.., ..]).fill(1);
+        assert_eq!(array.as_flattened(), &[7, 1, 1, 7, 1, 1]);
+
+        let row = Array::new_by_enumeration([3, 1], |i| i);
+        array.assign(row.view());
+        assert_eq!(array.as_flattened(), &[0, 1, 2, 0, 1, 2]);
+        let other = Array::new_by_enumeration([2, 3], |i| i * 10).transpose();
+        array.assign(other.view());
+        assert_eq!(array.as_flattened(), &[0, 20, 40, 10, 30, 50]);
+        assert_eq!(
+            array.try_assign(Array::new_with([2, 2], 0).view()),
+            Err(Error::IncompatibleShapes {
+                axis: 0,
+                left: 2,
+                right: 3
+            })
+        );
+    }
+
+    #[test]
+    fn copy_region() {
+        let mut framebuffer = Array::new_with([5, 4], '.');
+        let sprite = Array::new_with([2, 3], '#');
+        framebuffer.copy_region(sprite.view(), [3, 1]).unwrap();
+        assert_eq!(framebuffer[[3, 1]], '#');
+        assert_eq!(framebuffer[[4, 3]], '#');
+        assert_eq!(framebuffer[[2, 1]], '.');
+        assert_eq!(framebuffer[[3, 0]], '.');
+        assert_eq!(
+            framebuffer.copy_region(sprite.view(), [4, 0]),
+            Err(Error::OutOfBounds {
+                axis: 0,
+                index: 6,
+                len: 5
+            })
+        );
+        assert_eq!(
+            framebuffer.copy_region(sprite.view(), [usize::MAX, 0]),
+            Err(Error::OutOfBounds {
+                axis: 0,
+                index: usize::MAX,
+                len: 5
+            })
+        );
+        assert_eq!(framebuffer.iter().filter(|(_, x)| **x == '#').count(), 6);
+    }
+}
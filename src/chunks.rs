@@ -0,0 +1,250 @@
+use std::marker::PhantomData;
+
+use crate::{array::Allocator, iterator::Cursor, Array, ArrayView, ArrayViewMut};
+
+/// Iterator over disjoint tiles of a view, see [`ArrayView::chunks`].
+pub struct Chunks<'a, T, const D: usize> {
+    cursor: Cursor<D>,
+    ptr: *const T,
+    view_size: [usize; D],
+    size: [usize; D],
+    stride: [isize; D],
+    phantom: PhantomData<&'a T>,
+}
+
+/// Iterator over disjoint mutable tiles of a view, see [`ArrayViewMut::chunks_mut`].
+pub struct ChunksMut<'a, T, const D: usize> {
+    cursor: Cursor<D>,
+    ptr: *mut T,
+    view_size: [usize; D],
+    size: [usize; D],
+    stride: [isize; D],
+    phantom: PhantomData<&'a mut T>,
+}
+
+unsafe impl<T: Sync, const D: usize> Send for Chunks<'_, T, D> {}
+unsafe impl<T: Sync, const D: usize> Sync for Chunks<'_, T, D> {}
+unsafe impl<T: Send, const D: usize> Send for ChunksMut<'_, T, D> {}
+unsafe impl<T: Sync, const D: usize> Sync for ChunksMut<'_, T, D> {}
+
+/// Returns the number of chunks of `size` along every axis and the stride between them. If
+/// `exact` is set, partial chunks at the edges are left out.
+pub(crate) fn chunk_grid<const D: usize>(
+    view_size: [usize; D],
+    stride: [isize; D],
+    size: [usize; D],
+    exact: bool,
+) -> ([usize; D], [isize; D]) {
+    let mut grid = [0; D];
+    let mut grid_stride = [0; D];
+    for i in 0..D {
+        assert_ne!(size[i], 0, "Chunk size of axis {i} is 0");
+        grid[i] = if exact {
+            view_size[i] / size[i]
+        } else {
+            view_size[i].div_ceil(size[i])
+        };
+        grid_stride[i] = stride[i] * size[i] as isize;
+    }
+    (grid, grid_stride)
+}
+
+/// Returns the cursor over the first elements of all chunks of `size`.
+fn chunk_cursor<const D: usize>(
+    view_size: [usize; D],
+    stride: [isize; D],
+    size: [usize; D],
+    exact: bool,
+) -> Cursor<D> {
+    let (grid, grid_stride) = chunk_grid(view_size, stride, size, exact);
+    Cursor::new(grid, grid_stride)
+}
+
+/// The size of the chunk at `loc` of the chunk grid, which is smaller than `size` at the edges.
+pub(crate) fn chunk_size<const D: usize>(
+    view_size: [usize; D],
+    size: [usize; D],
+    loc: [usize; D],
+) -> [usize; D] {
+    let mut r = size;
+    for i in 0..D {
+        r[i] = r[i].min(view_size[i] - loc[i] * size[i]);
+    }
+    r
+}
+
+impl<'a, T, const D: usize> Iterator for Chunks<'a, T, D> {
+    type Item = ArrayView<'a, T, D>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.cursor.next().map(|(loc, offset)| {
+            // SAFETY the chunk is cut to fit into the view
+            unsafe {
+                ArrayView::from_raw_parts(
+                    self.ptr.offset(offset),
+                    chunk_size(self.view_size, self.size, loc),
+                    self.stride,
+                )
+            }
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.cursor.size_hint()
+    }
+}
+
+impl<T, const D: usize> ExactSizeIterator for Chunks<'_, T, D> {}
+
+impl<'a, T, const D: usize> Iterator for ChunksMut<'a, T, D> {
+    type Item = ArrayViewMut<'a, T, D>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.cursor.next().map(|(loc, offset)| {
+            // SAFETY the chunk is cut to fit into the view, and chunks never overlap
+            unsafe {
+                ArrayViewMut::from_raw_parts(
+                    self.ptr.offset(offset),
+                    chunk_size(self.view_size, self.size, loc),
+                    self.stride,
+                )
+            }
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.cursor.size_hint()
+    }
+}
+
+impl<T, const D: usize> ExactSizeIterator for ChunksMut<'_, T, D> {}
+
+impl<'a, T, const D: usize> ArrayView<'a, T, D> {
+    /// Splits the view into disjoint tiles of `size`, in x-first order. Tiles at the far edges
+    /// are smaller if the size of the view is not a multiple of `size`. Panics if any length of
+    /// `size` is 0.
+    pub fn chunks(&self, size: [usize; D]) -> Chunks<'a, T, D> {
+        Chunks {
+            cursor: chunk_cursor(self.size, self.stride, size, false),
+            ptr: self.ptr,
+            view_size: self.size,
+            size,
+            stride: self.stride,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Like [`chunks`](Self::chunks), but leaves out the smaller tiles at the edges.
+    pub fn exact_chunks(&self, size: [usize; D]) -> Chunks<'a, T, D> {
+        Chunks {
+            cursor: chunk_cursor(self.size, self.stride, size, true),
+            ptr: self.ptr,
+            view_size: self.size,
+            size,
+            stride: self.stride,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<'a, T, const D: usize> ArrayViewMut<'a, T, D> {
+    /// Splits the view into disjoint tiles of `size`, see [`ArrayView::chunks`].
+    pub fn chunks(&self, size: [usize; D]) -> Chunks<'_, T, D> {
+        self.view().chunks(size)
+    }
+
+    /// Splits the view into disjoint tiles of `size`, see [`ArrayView::exact_chunks`].
+    pub fn exact_chunks(&self, size: [usize; D]) -> Chunks<'_, T, D> {
+        self.view().exact_chunks(size)
+    }
+
+    /// Splits the view into disjoint mutable tiles of `size`, see [`ArrayView::chunks`]. As the
+    /// tiles never overlap, they can be handed to different threads.
+    pub fn chunks_mut(&mut self, size: [usize; D]) -> ChunksMut<'_, T, D> {
+        self.view_mut().into_chunks_mut(size)
+    }
+
+    /// Splits the view into disjoint mutable tiles of `size`, see [`ArrayView::exact_chunks`].
+    pub fn exact_chunks_mut(&mut self, size: [usize; D]) -> ChunksMut<'_, T, D> {
+        self.view_mut().into_exact_chunks_mut(size)
+    }
+
+    /// Like [`chunks_mut`](Self::chunks_mut), but keeps the lifetime of this view.
+    pub fn into_chunks_mut(self, size: [usize; D]) -> ChunksMut<'a, T, D> {
+        ChunksMut {
+            cursor: chunk_cursor(self.size, self.stride, size, false),
+            ptr: self.ptr,
+            view_size: self.size,
+            size,
+            stride: self.stride,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Like [`exact_chunks_mut`](Self::exact_chunks_mut), but keeps the lifetime of this view.
+    pub fn into_exact_chunks_mut(self, size: [usize; D]) -> ChunksMut<'a, T, D> {
+        ChunksMut {
+            cursor: chunk_cursor(self.size, self.stride, size, true),
+            ptr: self.ptr,
+            view_size: self.size,
+            size,
+            stride: self.stride,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<T, const D: usize, A: Allocator> Array<T, D, A> {
+    /// Splits the array into disjoint tiles of `size`, see [`ArrayView::chunks`].
+    pub fn chunks(&self, size: [usize; D]) -> Chunks<'_, T, D> {
+        self.view().chunks(size)
+    }
+
+    /// Splits the array into disjoint tiles of `size`, see [`ArrayView::exact_chunks`].
+    pub fn exact_chunks(&self, size: [usize; D]) -> Chunks<'_, T, D> {
+        self.view().exact_chunks(size)
+    }
+
+    /// Splits the array into disjoint mutable tiles of `size`, see [`ArrayView::chunks`]. As
+    /// the tiles never overlap, they can be handed to different threads.
+    pub fn chunks_mut(&mut self, size: [usize; D]) -> ChunksMut<'_, T, D> {
+        self.view_mut().into_chunks_mut(size)
+    }
+
+    /// Splits the array into disjoint mutable tiles of `size`, see
+    /// [`ArrayView::exact_chunks`].
+    pub fn exact_chunks_mut(&mut self, size: [usize; D]) -> ChunksMut<'_, T, D> {
+        self.view_mut().into_exact_chunks_mut(size)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Array;
+
+    #[test]
+    fn chunks() {
+        let array = Array::new_by_enumeration([5, 3], |i| i);
+        let sizes = array
+            .chunks([2, 2])
+            .map(|chunk| chunk.size())
+            .collect::<Vec<_>>();
+        assert_eq!(sizes, vec![[2, 2], [2, 2], [1, 2], [2, 1], [2, 1], [1, 1]]);
+        assert_eq!(array.chunks([2, 2]).nth(4).unwrap()[[1, 0]], 13);
+        assert_eq!(array.exact_chunks([2, 2]).len(), 2);
+        assert_eq!(array.exact_chunks([6, 1]).len(), 0);
+    }
+
+    #[test]
+    fn chunks_mut_threads() {
+        let mut array = Array::new_with([8, 8], 0);
+        std::thread::scope(|scope| {
+            for (i, mut chunk) in array.chunks_mut([4, 3]).enumerate() {
+                scope.spawn(move || chunk.iter_mut().for_each(|(_, x)| *x = i));
+            }
+        });
+        assert_eq!(array[[0, 0]], 0);
+        assert_eq!(array[[5, 2]], 1);
+        assert_eq!(array[[7, 7]], 5);
+    }
+}
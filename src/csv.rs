@@ -0,0 +1,250 @@
+//! Reading and writing 2D arrays as delimited text. Every line of the text is a row, so the
+//! first axis of the array is the column and the second axis is the line. Quoted fields are
+//! not supported.
+
+use std::{
+    fmt::Display,
+    io::{self, BufRead, Write},
+    str::FromStr,
+};
+
+use crate::{array::Allocator, Array, ArrayView};
+
+/// How [`Array::from_csv`] splits text into elements.
+#[derive(Clone, Copy, Debug)]
+pub struct CsvOptions {
+    delimiter: Option<char>,
+    skip_lines: usize,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        Self {
+            delimiter: Some(','),
+            skip_lines: 0,
+        }
+    }
+}
+
+impl CsvOptions {
+    /// Comma-separated values without a header.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Separates values by `delimiter` instead of commas.
+    pub fn delimiter(mut self, delimiter: char) -> Self {
+        self.delimiter = Some(delimiter);
+        self
+    }
+
+    /// Separates values by any amount of whitespace instead of a delimiter.
+    pub fn whitespace(mut self) -> Self {
+        self.delimiter = None;
+        self
+    }
+
+    /// Ignores the first `lines` lines, which usually contain a header.
+    pub fn skip_header(mut self, lines: usize) -> Self {
+        self.skip_lines = lines;
+        self
+    }
+}
+
+/// The error type of [`Array::from_csv`]. Lines and columns start at 1, and lines include the
+/// skipped header.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum CsvError {
+    /// Reading from the underlying reader failed.
+    Io(io::Error),
+    /// A line had `got` values, but the first row had `expected`.
+    RaggedRow {
+        line: usize,
+        expected: usize,
+        got: usize,
+    },
+    /// A value could not be parsed as an element.
+    Parse {
+        line: usize,
+        column: usize,
+        value: String,
+    },
+}
+
+impl Display for CsvError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CsvError::Io(e) => write!(f, "I/O error: {e}"),
+            CsvError::RaggedRow {
+                line,
+                expected,
+                got,
+            } => write!(
+                f,
+                "Line {line} has {got} values, but previous lines have {expected}"
+            ),
+            CsvError::Parse {
+                line,
+                column,
+                value,
+            } => write!(f, "Can not parse {value:?} in line {line}, column {column}"),
+        }
+    }
+}
+
+impl std::error::Error for CsvError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            CsvError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for CsvError {
+    fn from(e: io::Error) -> Self {
+        CsvError::Io(e)
+    }
+}
+
+impl<T: FromStr> Array<T, 2> {
+    /// Reads a table of values, one row per line, into an array of size `[columns, rows]`.
+    /// Blank lines are ignored. Returns an error if a row has a different number of values than
+    /// the first one.
+    pub fn from_csv<R: BufRead>(reader: R, options: CsvOptions) -> Result<Self, CsvError> {
+        let mut data = Vec::new();
+        let mut columns = None;
+        let mut rows = 0;
+        for (i, line) in reader.lines().enumerate().skip(options.skip_lines) {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let values: Box<dyn Iterator<Item = &str>> = match options.delimiter {
+                Some(delimiter) => Box::new(line.split(delimiter)),
+                None => Box::new(line.split_whitespace()),
+            };
+            let len_before = data.len();
+            for (column, value) in values.enumerate() {
+                let value = value.trim();
+                data.push(value.parse().map_err(|_| CsvError::Parse {
+                    line: i + 1,
+                    column: column + 1,
+                    value: value.to_owned(),
+                })?);
+            }
+            let got = data.len() - len_before;
+            let expected = *columns.get_or_insert(got);
+            if got != expected {
+                return Err(CsvError::RaggedRow {
+                    line: i + 1,
+                    expected,
+                    got,
+                });
+            }
+            rows += 1;
+        }
+        Ok(Array::from_flat(data, [columns.unwrap_or(0), rows]).unwrap())
+    }
+}
+
+impl<T: Display> ArrayView<'_, T, 2> {
+    /// Writes the view as comma-separated values, one row per line.
+    pub fn to_csv<W: Write>(&self, writer: W) -> io::Result<()> {
+        self.to_csv_with_delimiter(writer, ',')
+    }
+
+    /// Like [`to_csv`](Self::to_csv), but separates values by `delimiter`.
+    pub fn to_csv_with_delimiter<W: Write>(
+        &self,
+        mut writer: W,
+        delimiter: char,
+    ) -> io::Result<()> {
+        for row in self.lanes(0) {
+            let mut first = true;
+            for (_, x) in row.iter() {
+                if !first {
+                    write!(writer, "{delimiter}")?;
+                }
+                first = false;
+                write!(writer, "{x}")?;
+            }
+            writeln!(writer)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: Display, A: Allocator> Array<T, 2, A> {
+    /// Writes the array as comma-separated values, see [`ArrayView::to_csv`].
+    pub fn to_csv<W: Write>(&self, writer: W) -> io::Result<()> {
+        self.view().to_csv(writer)
+    }
+
+    /// Writes the array as values separated by `delimiter`, see
+    /// [`ArrayView::to_csv_with_delimiter`].
+    pub fn to_csv_with_delimiter<W: Write>(&self, writer: W, delimiter: char) -> io::Result<()> {
+        self.view().to_csv_with_delimiter(writer, delimiter)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Array, CsvError, CsvOptions};
+
+    #[test]
+    fn round_trip() {
+        let text = "x,y,z\n1, 2,3\n\n4,5,6\n";
+        let array =
+            Array::<u8, 2>::from_csv(text.as_bytes(), CsvOptions::new().skip_header(1)).unwrap();
+        assert_eq!(array.size(), [3, 2]);
+        assert_eq!(array[[1, 0]], 2);
+        assert_eq!(array[[0, 1]], 4);
+        let mut out = Vec::new();
+        array.to_csv(&mut out).unwrap();
+        assert_eq!(out, b"1,2,3\n4,5,6\n");
+
+        let array =
+            Array::<i32, 2>::from_csv("1  -2\t3\n4 5 6".as_bytes(), CsvOptions::new().whitespace())
+                .unwrap();
+        assert_eq!(array[[1, 0]], -2);
+        let mut out = Vec::new();
+        array
+            .transpose()
+            .to_csv_with_delimiter(&mut out, ';')
+            .unwrap();
+        assert_eq!(out, b"1;4\n-2;5\n3;6\n");
+
+        let mut out = Vec::new();
+        Array::<u8, 2>::new([3, 0])
+            .transpose()
+            .to_csv(&mut out)
+            .unwrap();
+        assert_eq!(out, b"\n\n\n");
+    }
+
+    #[test]
+    fn errors() {
+        let error =
+            Array::<u8, 2>::from_csv("1;2\n3;4\n5\n".as_bytes(), CsvOptions::new().delimiter(';'))
+                .err()
+                .unwrap();
+        assert!(matches!(
+            error,
+            CsvError::RaggedRow {
+                line: 3,
+                expected: 2,
+                got: 1
+            }
+        ));
+        let error =
+            Array::<u8, 2>::from_csv("h\n1,x\n".as_bytes(), CsvOptions::new().skip_header(1))
+                .err()
+                .unwrap();
+        assert!(matches!(
+            error,
+            CsvError::Parse { line: 2, column: 2, value } if value == "x"
+        ));
+    }
+}
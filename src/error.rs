@@ -0,0 +1,65 @@
+use std::fmt::Display;
+
+/// The error type of all fallible operations of this crate.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    /// An index was not within `0..len` along an axis.
+    OutOfBounds {
+        axis: usize,
+        index: usize,
+        len: usize,
+    },
+    /// A shape with `expected` elements was given `got` elements.
+    ShapeMismatch { expected: usize, got: usize },
+    /// The number of elements of a shape does not fit into a `usize`.
+    SizeOverflow,
+    /// An axis argument was not within `0..dimensions`.
+    InvalidAxis { axis: usize, dimensions: usize },
+    /// A slice had a step size of 0.
+    ZeroStep { axis: usize },
+    /// Two arrays had different sizes along an axis.
+    IncompatibleShapes {
+        axis: usize,
+        left: usize,
+        right: usize,
+    },
+    /// An operation that combines several arrays was given none.
+    NoArrays,
+    /// Data with `got` axes was given to an array with `expected` axes.
+    RankMismatch { expected: usize, got: usize },
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            Error::OutOfBounds { axis, index, len } => write!(
+                f,
+                "Array index of axis {axis} is out of bounds! 0..{len}.contains({index}) == false"
+            ),
+            Error::ShapeMismatch { expected, got } => write!(
+                f,
+                "Shape with {expected} elements can not be used for {got} elements"
+            ),
+            Error::SizeOverflow => write!(f, "Number of elements of the shape overflows usize"),
+            Error::InvalidAxis { axis, dimensions } => {
+                write!(
+                    f,
+                    "Axis {axis} does not exist in an array of {dimensions} axes"
+                )
+            }
+            Error::ZeroStep { axis } => write!(f, "Slice of axis {axis} has a step size of 0"),
+            Error::IncompatibleShapes { axis, left, right } => write!(
+                f,
+                "Sizes {left} and {right} of axis {axis} are incompatible"
+            ),
+            Error::NoArrays => write!(f, "No arrays were given"),
+            Error::RankMismatch { expected, got } => write!(
+                f,
+                "An array of {got} axes can not be read into an array of {expected} axes"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
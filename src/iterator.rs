@@ -1,69 +1,110 @@
-use std::slice;
+use std::marker::PhantomData;
 
-use crate::Array;
-// trickery!!
-use crate::array::Allocator;
+/// Computes the coordinate and offset of the element at `index` in x-first order in O(D).
+#[inline]
+pub(crate) fn locate<const D: usize>(
+    size: [usize; D],
+    stride: [isize; D],
+    mut index: usize,
+) -> ([usize; D], isize) {
+    let mut loc = [0; D];
+    let mut offset = 0;
+    #[allow(clippy::needless_range_loop)] // clippy bug
+    for i in 0..D {
+        loc[i] = index % size[i];
+        index /= size[i];
+        offset += loc[i] as isize * stride[i];
+    }
+    (loc, offset)
+}
 
-pub struct Iter<I: Iterator, const D: usize> {
+/// Walks the coordinates of an array in x-first order and keeps track of the memory offset
+/// belonging to the current coordinate. This is shared by all iterators of this crate. The
+/// cursor can be walked from both ends, `ptr` is the front and `back` the back coordinate.
+#[derive(Clone)]
+pub(crate) struct Cursor<const D: usize> {
     pub(crate) size: [usize; D],
+    pub(crate) stride: [isize; D],
     pub(crate) ptr: [usize; D],
-    pub(crate) internal_iter: I,
+    pub(crate) offset: isize,
+    pub(crate) back: [usize; D],
+    pub(crate) back_offset: isize,
+    /// Flat index of `ptr`.
+    pub(crate) index: usize,
+    /// Flat index one past `back`.
+    pub(crate) end: usize,
 }
 
-impl<'a, T, const D: usize> Iter<slice::Iter<'a, T>, D> {
-    pub(crate) fn new<A: Allocator>(array: &'a Array<T, D, A>) -> Self {
-        Self {
-            size: array.size,
+impl<const D: usize> Cursor<D> {
+    pub(crate) fn new(size: [usize; D], stride: [isize; D]) -> Self {
+        let end = size.iter().product();
+        let mut r = Self {
+            size,
+            stride,
             ptr: [0; D],
-            internal_iter: array.data.iter(),
+            offset: 0,
+            back: [0; D],
+            back_offset: 0,
+            index: 0,
+            end,
+        };
+        if end != 0 {
+            (r.back, r.back_offset) = r.locate(end - 1);
         }
+        r
     }
-}
 
-impl<'a, T, const D: usize> Iter<slice::IterMut<'a, T>, D> {
-    pub(crate) fn new_mut<A: Allocator>(array: &'a mut Array<T, D, A>) -> Self {
-        Self {
-            size: array.size,
-            ptr: [0; D],
-            internal_iter: array.data.iter_mut(),
-        }
+    #[inline]
+    fn locate(&self, index: usize) -> ([usize; D], isize) {
+        locate(self.size, self.stride, index)
     }
-}
 
-impl<I: Iterator, const D: usize> Iter<I, D> {
     #[inline]
     fn increment_ptr(&mut self) {
         // propagate change
         // match common lengths
         match D {
             0 => panic!("invalid array dimensions: 0"),
-            1 => self.ptr[0] += 1,
+            1 => {
+                self.ptr[0] += 1;
+                self.offset += self.stride[0];
+            }
             2 => {
                 self.ptr[0] += 1;
+                self.offset += self.stride[0];
                 if self.ptr[0] == self.size[0] {
                     self.ptr[0] = 0;
+                    self.offset -= self.stride[0] * self.size[0] as isize;
                     self.ptr[1] += 1;
+                    self.offset += self.stride[1];
                 }
             }
             3 => {
                 self.ptr[0] += 1;
+                self.offset += self.stride[0];
                 if self.ptr[0] != self.size[0] {
                     return;
                 }
                 self.ptr[0] = 0;
+                self.offset -= self.stride[0] * self.size[0] as isize;
                 self.ptr[1] += 1;
+                self.offset += self.stride[1];
                 if self.ptr[1] != self.size[1] {
                     return;
                 }
                 self.ptr[1] = 0;
+                self.offset -= self.stride[1] * self.size[1] as isize;
                 self.ptr[2] += 1;
+                self.offset += self.stride[2];
             }
             _ => {
                 #[allow(clippy::needless_range_loop)] // clippy bug
                 for n in 0..D {
                     self.ptr[n] += 1;
+                    self.offset += self.stride[n];
                     if self.ptr[n] == self.size[n] {
                         self.ptr[n] = 0;
+                        self.offset -= self.stride[n] * self.size[n] as isize;
                     } else {
                         break;
                     }
@@ -71,20 +112,319 @@ impl<I: Iterator, const D: usize> Iter<I, D> {
             }
         }
     }
+
+    #[inline]
+    fn decrement_back(&mut self) {
+        #[allow(clippy::needless_range_loop)] // clippy bug
+        for n in 0..D {
+            if self.back[n] == 0 {
+                self.back[n] = self.size[n] - 1;
+                self.back_offset += self.stride[n] * (self.size[n] - 1) as isize;
+            } else {
+                self.back[n] -= 1;
+                self.back_offset -= self.stride[n];
+                break;
+            }
+        }
+    }
+}
+
+impl<const D: usize> Iterator for Cursor<D> {
+    type Item = ([usize; D], isize);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index == self.end {
+            return None;
+        }
+        let r = Some((self.ptr, self.offset));
+        self.index += 1;
+        if self.index != self.end {
+            self.increment_ptr();
+        }
+        r
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.end - self.index;
+        (remaining, Some(remaining))
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if n >= self.end - self.index {
+            self.index = self.end;
+            return None;
+        }
+        if n != 0 {
+            self.index += n;
+            (self.ptr, self.offset) = self.locate(self.index);
+        }
+        self.next()
+    }
+
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+}
+
+impl<const D: usize> DoubleEndedIterator for Cursor<D> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.index == self.end {
+            return None;
+        }
+        let r = Some((self.back, self.back_offset));
+        self.end -= 1;
+        if self.index != self.end {
+            self.decrement_back();
+        }
+        r
+    }
+
+    #[inline]
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        if n >= self.end - self.index {
+            self.end = self.index;
+            return None;
+        }
+        if n != 0 {
+            self.end -= n;
+            (self.back, self.back_offset) = self.locate(self.end - 1);
+        }
+        self.next_back()
+    }
+}
+
+impl<const D: usize> ExactSizeIterator for Cursor<D> {}
+
+/// Iterator over the locations of all elements of an [`Array`](crate::Array), see
+/// [`Array::indices`](crate::Array::indices).
+#[derive(Clone)]
+pub struct Indices<const D: usize> {
+    cursor: Cursor<D>,
+}
+
+impl<const D: usize> Indices<D> {
+    pub(crate) fn new(size: [usize; D]) -> Self {
+        Self {
+            cursor: Cursor::new(size, [0; D]),
+        }
+    }
 }
 
-impl<I: Iterator, const D: usize> Iterator for Iter<I, D> {
-    type Item = ([usize; D], I::Item);
+impl<const D: usize> Iterator for Indices<D> {
+    type Item = [usize; D];
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
-        self.internal_iter
-            .next()
-            .map(|x| {
-                let r = Some((self.ptr, x));
-                self.increment_ptr();
-                r
-            })
-            .unwrap_or(None)
+        self.cursor.next().map(|(loc, _)| loc)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.cursor.size_hint()
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.cursor.nth(n).map(|(loc, _)| loc)
+    }
+
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+}
+
+impl<const D: usize> DoubleEndedIterator for Indices<D> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.cursor.next_back().map(|(loc, _)| loc)
+    }
+
+    #[inline]
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.cursor.nth_back(n).map(|(loc, _)| loc)
+    }
+}
+
+impl<const D: usize> ExactSizeIterator for Indices<D> {}
+
+/// Iterator over the elements of an [`Array`](crate::Array) or
+/// [`ArrayView`](crate::ArrayView), yielding each element together with its location.
+pub struct Iter<'a, T, const D: usize> {
+    pub(crate) cursor: Cursor<D>,
+    pub(crate) base: *const T,
+    pub(crate) phantom: PhantomData<&'a T>,
+}
+
+impl<'a, T, const D: usize> Iter<'a, T, D> {
+    /// # Safety
+    /// Every location within `size` must map to a valid element through `stride`.
+    pub(crate) unsafe fn new(base: *const T, size: [usize; D], stride: [isize; D]) -> Self {
+        Self {
+            cursor: Cursor::new(size, stride),
+            base,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<'a, T, const D: usize> Iterator for Iter<'a, T, D> {
+    type Item = ([usize; D], &'a T);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.cursor.next().map(|(loc, offset)| {
+            // SAFETY the cursor only yields offsets of valid locations
+            (loc, unsafe { &*self.base.offset(offset) })
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.cursor.size_hint()
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.cursor.nth(n).map(|(loc, offset)| {
+            // SAFETY the cursor only yields offsets of valid locations
+            (loc, unsafe { &*self.base.offset(offset) })
+        })
+    }
+
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+}
+
+impl<T, const D: usize> DoubleEndedIterator for Iter<'_, T, D> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.cursor.next_back().map(|(loc, offset)| {
+            // SAFETY the cursor only yields offsets of valid locations
+            (loc, unsafe { &*self.base.offset(offset) })
+        })
+    }
+
+    #[inline]
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.cursor.nth_back(n).map(|(loc, offset)| {
+            // SAFETY the cursor only yields offsets of valid locations
+            (loc, unsafe { &*self.base.offset(offset) })
+        })
+    }
+}
+
+impl<T, const D: usize> ExactSizeIterator for Iter<'_, T, D> {}
+
+impl<T, const D: usize> Clone for Iter<'_, T, D> {
+    fn clone(&self) -> Self {
+        Self {
+            cursor: self.cursor.clone(),
+            base: self.base,
+            phantom: PhantomData,
+        }
+    }
+}
+
+/// Mutable iterator over the elements of an [`Array`](crate::Array) or
+/// [`ArrayViewMut`](crate::ArrayViewMut), yielding each element together with its location.
+pub struct IterMut<'a, T, const D: usize> {
+    pub(crate) cursor: Cursor<D>,
+    pub(crate) base: *mut T,
+    pub(crate) phantom: PhantomData<&'a mut T>,
+}
+
+impl<'a, T, const D: usize> IterMut<'a, T, D> {
+    /// # Safety
+    /// Every location within `size` must map to a valid element through `stride`, and no two
+    /// locations may map to the same element.
+    pub(crate) unsafe fn new(base: *mut T, size: [usize; D], stride: [isize; D]) -> Self {
+        Self {
+            cursor: Cursor::new(size, stride),
+            base,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<'a, T, const D: usize> Iterator for IterMut<'a, T, D> {
+    type Item = ([usize; D], &'a mut T);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.cursor.next().map(|(loc, offset)| {
+            // SAFETY the cursor only yields offsets of valid locations, and each of them only once
+            (loc, unsafe { &mut *self.base.offset(offset) })
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.cursor.size_hint()
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.cursor.nth(n).map(|(loc, offset)| {
+            // SAFETY the cursor only yields offsets of valid locations, and each of them only once
+            (loc, unsafe { &mut *self.base.offset(offset) })
+        })
+    }
+
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+}
+
+impl<T, const D: usize> DoubleEndedIterator for IterMut<'_, T, D> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.cursor.next_back().map(|(loc, offset)| {
+            // SAFETY the cursor only yields offsets of valid locations, and each of them only once
+            (loc, unsafe { &mut *self.base.offset(offset) })
+        })
+    }
+
+    #[inline]
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.cursor.nth_back(n).map(|(loc, offset)| {
+            // SAFETY the cursor only yields offsets of valid locations, and each of them only once
+            (loc, unsafe { &mut *self.base.offset(offset) })
+        })
+    }
+}
+
+impl<T, const D: usize> ExactSizeIterator for IterMut<'_, T, D> {}
+
+unsafe impl<T: Sync, const D: usize> Send for Iter<'_, T, D> {}
+unsafe impl<T: Sync, const D: usize> Sync for Iter<'_, T, D> {}
+unsafe impl<T: Send, const D: usize> Send for IterMut<'_, T, D> {}
+unsafe impl<T: Sync, const D: usize> Sync for IterMut<'_, T, D> {}
+
+#[cfg(test)]
+mod test {
+    use crate::Array;
+
+    #[test]
+    fn double_ended() {
+        let array = Array::new_by_enumeration([3, 4, 2], |i| i);
+        let forward = array.iter().map(|(_, x)| *x).collect::<Vec<_>>();
+        let mut backward = array.iter().rev().map(|(_, x)| *x).collect::<Vec<_>>();
+        backward.reverse();
+        assert_eq!(forward, backward);
+        assert_eq!(array.iter().len(), 24);
+        assert_eq!(array.iter().last(), Some(([2, 3, 1], &23)));
+
+        let mut iter = array.iter();
+        assert_eq!(iter.nth(13), Some(([1, 0, 1], &13)));
+        assert_eq!(iter.next_back(), Some(([2, 3, 1], &23)));
+        assert_eq!(iter.nth_back(3), Some(([1, 2, 1], &19)));
+        assert_eq!(iter.len(), 5);
+        assert_eq!(iter.nth(5), None);
+        assert_eq!(iter.next_back(), None);
+
+        let mut permuted = array.permuted_axes([2, 0, 1]);
+        permuted.iter_mut().rev().for_each(|(_, x)| *x += 1);
+        assert_eq!(permuted.iter().nth(5), Some(([1, 2, 0], &15)));
     }
 }
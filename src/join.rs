@@ -0,0 +1,260 @@
+//! Joining several arrays into one and splitting arrays into parts along an axis.
+
+use crate::{
+    array::{standard_stride, Allocator},
+    iterator::{locate, Iter},
+    view::check_axis,
+    Array, ArrayView, ArrayViewMut, Error,
+};
+
+/// Joins `views` along `axis` into a new array in standard layout. All views must have the
+/// same size along every other axis.
+pub fn concatenate<T: Clone, const D: usize>(
+    axis: usize,
+    views: &[ArrayView<'_, T, D>],
+) -> Result<Array<T, D>, Error> {
+    if axis >= D {
+        return Err(Error::InvalidAxis {
+            axis,
+            dimensions: D,
+        });
+    }
+    let first = views.first().ok_or(Error::NoArrays)?;
+    let mut size = first.size;
+    size[axis] = 0;
+    for view in views {
+        for i in 0..D {
+            if i != axis && view.size[i] != first.size[i] {
+                return Err(Error::IncompatibleShapes {
+                    axis: i,
+                    left: first.size[i],
+                    right: view.size[i],
+                });
+            }
+        }
+        size[axis] += view.size[axis];
+    }
+    let stride = standard_stride(size)?.0;
+
+    // in x-first order, every block of the axes up to `axis` is followed by the block of the
+    // next view, and all of them together by the next location of the outer axes
+    let mut outer = size;
+    for len in &mut outer[..=axis] {
+        *len = 1;
+    }
+    let mut data = Vec::with_capacity(size.iter().product());
+    for i in 0..outer.iter().product() {
+        // empty views have no elements to point at, and their pointer may be dangling
+        for view in views.iter().filter(|view| !view.size.contains(&0)) {
+            let (_, offset) = locate(outer, view.stride, i);
+            let mut inner = view.size;
+            for len in &mut inner[axis + 1..] {
+                *len = 1;
+            }
+            // SAFETY the block starts at a location of the view and does not leave it
+            let block = unsafe { Iter::new(view.ptr.offset(offset), inner, view.stride) };
+            data.extend(block.map(|(_, x)| x.clone()));
+        }
+    }
+    Ok(Array::from_parts(size, stride, data))
+}
+
+/// Joins `views` along a new `axis` of length `views.len()` into a new array in standard
+/// layout. All views must have the same size. The result has one more dimension than the
+/// views, which is checked at compile time.
+pub fn stack<T: Clone, const D: usize, const E: usize>(
+    axis: usize,
+    views: &[ArrayView<'_, T, D>],
+) -> Result<Array<T, E>, Error> {
+    const { assert!(D + 1 == E, "stack must increase the dimensionality by one") };
+    if axis >= E {
+        return Err(Error::InvalidAxis {
+            axis,
+            dimensions: E,
+        });
+    }
+    let expanded = views
+        .iter()
+        .map(|view| {
+            let mut size = [1; E];
+            let mut stride = [0; E];
+            for i in 0..D {
+                let j = if i < axis { i } else { i + 1 };
+                size[j] = view.size[i];
+                stride[j] = view.stride[i];
+            }
+            // SAFETY the new axis has a length of 1, so it maps to the same elements
+            unsafe { ArrayView::from_raw_parts(view.ptr, size, stride) }
+        })
+        .collect::<Vec<_>>();
+    concatenate(axis, &expanded)
+}
+
+/// Splits size and stride at `index` along `axis`, returning the offset of the second part.
+fn split_parts<const D: usize>(
+    size: [usize; D],
+    stride: [isize; D],
+    axis: usize,
+    index: usize,
+) -> ([usize; D], [usize; D], isize) {
+    check_axis::<D>(axis);
+    if index > size[axis] {
+        panic!(
+            "{}",
+            Error::OutOfBounds {
+                axis,
+                index,
+                len: size[axis] + 1
+            }
+        )
+    }
+    let mut left = size;
+    left[axis] = index;
+    let mut right = size;
+    right[axis] -= index;
+    // an empty part has no elements to point at, and the pointer may be dangling
+    let offset = if right.contains(&0) {
+        0
+    } else {
+        index as isize * stride[axis]
+    };
+    (left, right, offset)
+}
+
+impl<'a, T, const D: usize> ArrayView<'a, T, D> {
+    /// Splits the view into the parts before and after `index` along `axis`. Panics if `index`
+    /// is larger than the length of `axis`.
+    pub fn split_at(
+        &self,
+        axis: usize,
+        index: usize,
+    ) -> (ArrayView<'a, T, D>, ArrayView<'a, T, D>) {
+        let (left, right, offset) = split_parts(self.size, self.stride, axis, index);
+        // SAFETY both parts are within the view
+        unsafe {
+            (
+                ArrayView::from_raw_parts(self.ptr, left, self.stride),
+                ArrayView::from_raw_parts(self.ptr.offset(offset), right, self.stride),
+            )
+        }
+    }
+}
+
+impl<'a, T, const D: usize> ArrayViewMut<'a, T, D> {
+    /// Splits the view into the parts before and after `index` along `axis`, see
+    /// [`ArrayView::split_at`].
+    pub fn split_at(
+        &self,
+        axis: usize,
+        index: usize,
+    ) -> (ArrayView<'_, T, D>, ArrayView<'_, T, D>) {
+        self.view().split_at(axis, index)
+    }
+
+    /// Splits the view into two mutable parts before and after `index` along `axis`, see
+    /// [`ArrayView::split_at`].
+    pub fn split_at_mut(
+        &mut self,
+        axis: usize,
+        index: usize,
+    ) -> (ArrayViewMut<'_, T, D>, ArrayViewMut<'_, T, D>) {
+        self.view_mut().into_split_at(axis, index)
+    }
+
+    /// Like [`split_at_mut`](Self::split_at_mut), but keeps the lifetime of this view.
+    pub fn into_split_at(
+        self,
+        axis: usize,
+        index: usize,
+    ) -> (ArrayViewMut<'a, T, D>, ArrayViewMut<'a, T, D>) {
+        let (left, right, offset) = split_parts(self.size, self.stride, axis, index);
+        // SAFETY both parts are within the view and do not overlap
+        unsafe {
+            (
+                ArrayViewMut::from_raw_parts(self.ptr, left, self.stride),
+                ArrayViewMut::from_raw_parts(self.ptr.offset(offset), right, self.stride),
+            )
+        }
+    }
+}
+
+impl<T, const D: usize, A: Allocator> Array<T, D, A> {
+    /// Splits the array into the parts before and after `index` along `axis`, see
+    /// [`ArrayView::split_at`].
+    pub fn split_at(
+        &self,
+        axis: usize,
+        index: usize,
+    ) -> (ArrayView<'_, T, D>, ArrayView<'_, T, D>) {
+        self.view().split_at(axis, index)
+    }
+
+    /// Splits the array into two mutable parts before and after `index` along `axis`, see
+    /// [`ArrayView::split_at`].
+    pub fn split_at_mut(
+        &mut self,
+        axis: usize,
+        index: usize,
+    ) -> (ArrayViewMut<'_, T, D>, ArrayViewMut<'_, T, D>) {
+        self.view_mut().into_split_at(axis, index)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{concatenate, stack, Array, Error};
+
+    #[test]
+    fn concatenate_and_stack() {
+        let a = Array::new_by_enumeration([2, 2], |i| i);
+        let b = Array::new_by_enumeration([2, 1], |i| i + 10);
+        let c = concatenate(1, &[a.view(), b.view()]).unwrap();
+        assert_eq!(c.size(), [2, 3]);
+        assert_eq!(c.as_flattened(), &[0, 1, 2, 3, 10, 11]);
+
+        let t = b.clone().transpose();
+        let c = concatenate(0, &[a.view(), a.view(), t.view()]).unwrap();
+        assert_eq!(c.size(), [5, 2]);
+        assert_eq!(c.as_flattened(), &[0, 1, 0, 1, 10, 2, 3, 2, 3, 11]);
+
+        assert_eq!(
+            concatenate(0, &[a.view(), b.view()]).err(),
+            Some(Error::IncompatibleShapes {
+                axis: 1,
+                left: 2,
+                right: 1
+            })
+        );
+        assert_eq!(concatenate::<usize, 2>(0, &[]).err(), Some(Error::NoArrays));
+        let empty = Array::new([2, 0]).transpose();
+        let c = concatenate(0, &[a.view(), empty.view()]).unwrap();
+        assert_eq!(c.as_flattened(), a.as_flattened());
+
+        let s: Array<_, 3> = stack(2, &[a.view(), a.view()]).unwrap();
+        assert_eq!(s.size(), [2, 2, 2]);
+        assert_eq!(s[[1, 0, 1]], 1);
+        let s: Array<_, 3> = stack(0, &[a.view(), (&a * 2).view()]).unwrap();
+        assert_eq!(s.size(), [2, 2, 2]);
+        assert_eq!(s[[1, 1, 1]], 6);
+        assert_eq!(s[[0, 1, 1]], 3);
+    }
+
+    #[test]
+    fn split() {
+        let mut array = Array::new_by_enumeration([4, 3], |i| i);
+        let (left, right) = array.split_at(0, 1);
+        assert_eq!(left.size(), [1, 3]);
+        assert_eq!(right.size(), [3, 3]);
+        assert_eq!(right[[0, 1]], 5);
+
+        let (mut top, mut bottom) = array.split_at_mut(1, 3);
+        assert_eq!(bottom.size(), [4, 0]);
+        top[[3, 2]] = 100;
+        assert!(bottom.get_mut([0, 0]).is_none());
+        assert_eq!(array[[3, 2]], 100);
+
+        let empty = Array::<u32, 2>::new([3, 0]);
+        let (left, right) = empty.split_at(0, 2);
+        assert_eq!((left.size(), right.size()), ([2, 0], [1, 0]));
+    }
+}
@@ -0,0 +1,372 @@
+use std::marker::PhantomData;
+
+use crate::{array::Allocator, iterator::Cursor, view::check_axis, Array, ArrayView, ArrayViewMut};
+
+/// Iterator over the 1D lanes along one axis, see [`ArrayView::lanes`].
+pub struct Lanes<'a, T, const D: usize> {
+    cursor: Cursor<D>,
+    ptr: *const T,
+    len: usize,
+    stride: isize,
+    phantom: PhantomData<&'a T>,
+}
+
+/// Iterator over the mutable 1D lanes along one axis, see [`ArrayViewMut::lanes_mut`].
+pub struct LanesMut<'a, T, const D: usize> {
+    cursor: Cursor<D>,
+    ptr: *mut T,
+    len: usize,
+    stride: isize,
+    phantom: PhantomData<&'a mut T>,
+}
+
+/// Iterator over the sub-views for each index of one axis, see [`ArrayView::axis_iter`].
+pub struct AxisIter<'a, T, const E: usize> {
+    ptr: *const T,
+    size: [usize; E],
+    stride: [isize; E],
+    axis_stride: isize,
+    index: usize,
+    end: usize,
+    phantom: PhantomData<&'a T>,
+}
+
+/// Iterator over the mutable sub-views for each index of one axis, see
+/// [`ArrayViewMut::axis_iter_mut`].
+pub struct AxisIterMut<'a, T, const E: usize> {
+    ptr: *mut T,
+    size: [usize; E],
+    stride: [isize; E],
+    axis_stride: isize,
+    index: usize,
+    end: usize,
+    phantom: PhantomData<&'a mut T>,
+}
+
+unsafe impl<T: Sync, const D: usize> Send for Lanes<'_, T, D> {}
+unsafe impl<T: Sync, const D: usize> Sync for Lanes<'_, T, D> {}
+unsafe impl<T: Send, const D: usize> Send for LanesMut<'_, T, D> {}
+unsafe impl<T: Sync, const D: usize> Sync for LanesMut<'_, T, D> {}
+unsafe impl<T: Sync, const E: usize> Send for AxisIter<'_, T, E> {}
+unsafe impl<T: Sync, const E: usize> Sync for AxisIter<'_, T, E> {}
+unsafe impl<T: Send, const E: usize> Send for AxisIterMut<'_, T, E> {}
+unsafe impl<T: Sync, const E: usize> Sync for AxisIterMut<'_, T, E> {}
+
+/// Returns the cursor over the starts of all lanes along `axis` along with the lane length and
+/// stride.
+pub(crate) fn lane_parts<const D: usize>(
+    size: [usize; D],
+    stride: [isize; D],
+    axis: usize,
+) -> (Cursor<D>, usize, isize) {
+    check_axis::<D>(axis);
+    let mut lane_size = size;
+    lane_size[axis] = 1;
+    // the lanes of an empty view have no elements to point at, and the pointer may be dangling
+    let cursor_stride = if size.contains(&0) { [0; D] } else { stride };
+    (
+        Cursor::new(lane_size, cursor_stride),
+        size[axis],
+        stride[axis],
+    )
+}
+
+/// Removes `axis` from size and stride, returning them along with the length and stride of
+/// `axis`.
+fn axis_parts<const D: usize, const E: usize>(
+    size: [usize; D],
+    stride: [isize; D],
+    axis: usize,
+) -> ([usize; E], [isize; E], usize, isize) {
+    const {
+        assert!(
+            E + 1 == D,
+            "axis_iter must decrease the dimensionality by one"
+        )
+    };
+    check_axis::<D>(axis);
+    let mut new_size = [0; E];
+    let mut new_stride = [0; E];
+    for i in 0..E {
+        let j = if i < axis { i } else { i + 1 };
+        new_size[i] = size[j];
+        new_stride[i] = stride[j];
+    }
+    // the sub-views of an empty view have no elements to point at, and the pointer may be
+    // dangling
+    let axis_stride = if size.contains(&0) { 0 } else { stride[axis] };
+    (new_size, new_stride, size[axis], axis_stride)
+}
+
+impl<'a, T, const D: usize> Iterator for Lanes<'a, T, D> {
+    type Item = ArrayView<'a, T, 1>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.cursor.next().map(|(_, offset)| {
+            // SAFETY the lane starts at an element of the view and stays within it
+            unsafe { ArrayView::from_raw_parts(self.ptr.offset(offset), [self.len], [self.stride]) }
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.cursor.size_hint()
+    }
+}
+
+impl<T, const D: usize> ExactSizeIterator for Lanes<'_, T, D> {}
+
+impl<'a, T, const D: usize> Iterator for LanesMut<'a, T, D> {
+    type Item = ArrayViewMut<'a, T, 1>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.cursor.next().map(|(_, offset)| {
+            // SAFETY the lane starts at an element of the view and stays within it, and lanes
+            // never overlap
+            unsafe {
+                ArrayViewMut::from_raw_parts(self.ptr.offset(offset), [self.len], [self.stride])
+            }
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.cursor.size_hint()
+    }
+}
+
+impl<T, const D: usize> ExactSizeIterator for LanesMut<'_, T, D> {}
+
+impl<'a, T, const E: usize> Iterator for AxisIter<'a, T, E> {
+    type Item = ArrayView<'a, T, E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index == self.end {
+            return None;
+        }
+        let offset = self.index as isize * self.axis_stride;
+        self.index += 1;
+        // SAFETY the index is within the axis
+        unsafe {
+            Some(ArrayView::from_raw_parts(
+                self.ptr.offset(offset),
+                self.size,
+                self.stride,
+            ))
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.end - self.index, Some(self.end - self.index))
+    }
+}
+
+impl<T, const E: usize> DoubleEndedIterator for AxisIter<'_, T, E> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.index == self.end {
+            return None;
+        }
+        self.end -= 1;
+        let offset = self.end as isize * self.axis_stride;
+        // SAFETY the index is within the axis
+        unsafe {
+            Some(ArrayView::from_raw_parts(
+                self.ptr.offset(offset),
+                self.size,
+                self.stride,
+            ))
+        }
+    }
+}
+
+impl<T, const E: usize> ExactSizeIterator for AxisIter<'_, T, E> {}
+
+impl<'a, T, const E: usize> Iterator for AxisIterMut<'a, T, E> {
+    type Item = ArrayViewMut<'a, T, E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index == self.end {
+            return None;
+        }
+        let offset = self.index as isize * self.axis_stride;
+        self.index += 1;
+        // SAFETY the index is within the axis, and every index is only yielded once
+        unsafe {
+            Some(ArrayViewMut::from_raw_parts(
+                self.ptr.offset(offset),
+                self.size,
+                self.stride,
+            ))
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.end - self.index, Some(self.end - self.index))
+    }
+}
+
+impl<T, const E: usize> DoubleEndedIterator for AxisIterMut<'_, T, E> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.index == self.end {
+            return None;
+        }
+        self.end -= 1;
+        let offset = self.end as isize * self.axis_stride;
+        // SAFETY the index is within the axis, and every index is only yielded once
+        unsafe {
+            Some(ArrayViewMut::from_raw_parts(
+                self.ptr.offset(offset),
+                self.size,
+                self.stride,
+            ))
+        }
+    }
+}
+
+impl<T, const E: usize> ExactSizeIterator for AxisIterMut<'_, T, E> {}
+
+impl<'a, T, const D: usize> ArrayView<'a, T, D> {
+    /// Iterates over all 1D lanes along `axis`, for example over the rows of a 2D view with
+    /// `axis` 0, and over its columns with `axis` 1.
+    pub fn lanes(&self, axis: usize) -> Lanes<'a, T, D> {
+        let (cursor, len, stride) = lane_parts(self.size, self.stride, axis);
+        Lanes {
+            cursor,
+            ptr: self.ptr,
+            len,
+            stride,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Iterates over the sub-views for each index of `axis`, which have `axis` removed. For
+    /// example, this yields the columns of a 2D view with `axis` 0. `E` must be `D - 1`.
+    pub fn axis_iter<const E: usize>(&self, axis: usize) -> AxisIter<'a, T, E> {
+        let (size, stride, end, axis_stride) = axis_parts(self.size, self.stride, axis);
+        AxisIter {
+            ptr: self.ptr,
+            size,
+            stride,
+            axis_stride,
+            index: 0,
+            end,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<'a, T, const D: usize> ArrayViewMut<'a, T, D> {
+    /// Iterates over all 1D lanes along `axis`, see [`ArrayView::lanes`].
+    pub fn lanes(&self, axis: usize) -> Lanes<'_, T, D> {
+        self.view().lanes(axis)
+    }
+
+    /// Iterates over the sub-views for each index of `axis`, see [`ArrayView::axis_iter`].
+    pub fn axis_iter<const E: usize>(&self, axis: usize) -> AxisIter<'_, T, E> {
+        self.view().axis_iter(axis)
+    }
+
+    /// Iterates over all mutable 1D lanes along `axis`, see [`ArrayView::lanes`].
+    pub fn lanes_mut(&mut self, axis: usize) -> LanesMut<'_, T, D> {
+        self.view_mut().into_lanes_mut(axis)
+    }
+
+    /// Like [`lanes_mut`](Self::lanes_mut), but keeps the lifetime of this view.
+    pub fn into_lanes_mut(self, axis: usize) -> LanesMut<'a, T, D> {
+        let (cursor, len, stride) = lane_parts(self.size, self.stride, axis);
+        LanesMut {
+            cursor,
+            ptr: self.ptr,
+            len,
+            stride,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Iterates over the mutable sub-views for each index of `axis`, see
+    /// [`ArrayView::axis_iter`].
+    pub fn axis_iter_mut<const E: usize>(&mut self, axis: usize) -> AxisIterMut<'_, T, E> {
+        self.view_mut().into_axis_iter_mut(axis)
+    }
+
+    /// Like [`axis_iter_mut`](Self::axis_iter_mut), but keeps the lifetime of this view.
+    pub fn into_axis_iter_mut<const E: usize>(self, axis: usize) -> AxisIterMut<'a, T, E> {
+        let (size, stride, end, axis_stride) = axis_parts(self.size, self.stride, axis);
+        AxisIterMut {
+            ptr: self.ptr,
+            size,
+            stride,
+            axis_stride,
+            index: 0,
+            end,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<T, const D: usize, A: Allocator> Array<T, D, A> {
+    /// Iterates over all 1D lanes along `axis`, see [`ArrayView::lanes`].
+    pub fn lanes(&self, axis: usize) -> Lanes<'_, T, D> {
+        self.view().lanes(axis)
+    }
+
+    /// Iterates over all mutable 1D lanes along `axis`, see [`ArrayView::lanes`].
+    pub fn lanes_mut(&mut self, axis: usize) -> LanesMut<'_, T, D> {
+        self.view_mut().into_lanes_mut(axis)
+    }
+
+    /// Iterates over the sub-views for each index of `axis`, see [`ArrayView::axis_iter`].
+    pub fn axis_iter<const E: usize>(&self, axis: usize) -> AxisIter<'_, T, E> {
+        self.view().axis_iter(axis)
+    }
+
+    /// Iterates over the mutable sub-views for each index of `axis`, see
+    /// [`ArrayView::axis_iter`].
+    pub fn axis_iter_mut<const E: usize>(&mut self, axis: usize) -> AxisIterMut<'_, T, E> {
+        self.view_mut().into_axis_iter_mut(axis)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Array;
+
+    #[test]
+    fn lanes() {
+        let mut array = Array::new_by_enumeration([3, 2], |i| i);
+        let rows = array
+            .lanes(0)
+            .map(|lane| lane.iter().map(|x| *x.1).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        assert_eq!(rows, vec![vec![0, 1, 2], vec![3, 4, 5]]);
+        assert_eq!(array.lanes(1).len(), 3);
+        for mut column in array.lanes_mut(1) {
+            column[[1]] += 10;
+        }
+        assert_eq!(array.as_flattened(), &[0, 1, 2, 13, 14, 15]);
+
+        let mut empty = Array::<u32, 2>::new([3, 0]);
+        assert_eq!(empty.lanes(0).len(), 0);
+        assert!(empty.lanes(1).all(|lane| lane.size() == [0]));
+        assert_eq!(empty.lanes_mut(1).len(), 3);
+    }
+
+    #[test]
+    fn axis_iter() {
+        let mut array = Array::new_by_enumeration([2, 3, 2], |i| i);
+        let planes = array.axis_iter::<2>(2).collect::<Vec<_>>();
+        assert_eq!(planes.len(), 2);
+        assert_eq!(planes[1].size(), [2, 3]);
+        assert_eq!(planes[1][[1, 2]], 11);
+        let middle = array.axis_iter::<2>(1).nth(1).unwrap();
+        assert_eq!(middle.size(), [2, 2]);
+        assert_eq!(middle[[1, 1]], 9);
+        for (i, mut plane) in array.axis_iter_mut::<2>(0).rev().enumerate() {
+            plane.iter_mut().for_each(|(_, x)| *x = i);
+        }
+        assert_eq!(&array.as_flattened()[..4], &[1, 0, 1, 0]);
+
+        let mut empty = Array::<u32, 2>::new([3, 0]);
+        assert_eq!(empty.axis_iter::<1>(1).len(), 0);
+        assert!(empty.axis_iter::<1>(0).rev().all(|view| view.size() == [0]));
+        assert_eq!(empty.axis_iter_mut::<1>(0).count(), 3);
+    }
+}
@@ -1,13 +1,55 @@
 #![cfg_attr(feature = "allocator", feature(allocator_api))]
 
 mod array;
+mod assign;
+mod chunks;
+mod csv;
+mod error;
 mod iterator;
+mod join;
+mod lanes;
+mod npy;
+mod npz;
+mod ops;
+#[cfg(feature = "rayon")]
+/// micro_ndarray has been compiled with support for rayon. This adds parallel iterators such
+/// as [`Array::par_iter`]. rayon has been re-exported.
+mod par;
+mod raw;
+mod reduce;
+#[cfg(feature = "serde")]
+/// micro_ndarray has been compiled with support for serde. [`Array`] implements `Serialize` and
+/// `Deserialize` as its size and its elements in x-first order. serde has been re-exported.
+mod serde_impl;
+mod slice;
+mod transform;
 #[cfg(feature = "vec_split")]
 /// micro_ndarray has been compiled with support for vec_split.
 /// This means you can use vec_split's tools on this crate's
 /// [`Array`] struct. vec_split has been re-exported.
 mod vec_split_impl;
+mod view;
+mod windows;
+mod zip;
+#[cfg(feature = "rayon")]
+pub use rayon;
+#[cfg(feature = "serde")]
+pub use serde;
 #[cfg(feature = "vec_split")]
 pub use vec_split;
 
 pub use array::Array;
+pub use chunks::{Chunks, ChunksMut};
+pub use csv::{CsvError, CsvOptions};
+pub use error::Error;
+pub use iterator::{Indices, Iter, IterMut};
+pub use join::{concatenate, stack};
+pub use lanes::{AxisIter, AxisIterMut, Lanes, LanesMut};
+pub use npy::{NpyElement, NpyError};
+pub use npz::{NpzReader, NpzWriter};
+pub use ops::broadcast_size;
+pub use raw::{Pod, RawError};
+pub use slice::Slice;
+pub use view::{ArrayView, ArrayViewMut};
+pub use windows::{Neighbourhood, Neighbours, Windows};
+pub use zip::{Producer, Zip, ZipIter};
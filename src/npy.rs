@@ -0,0 +1,406 @@
+//! Reading and writing NumPy's `.npy` format. Arrays are written in Fortran order, which is the
+//! x-first order of this crate, so `array[[x, y]]` is `array[x, y]` in NumPy. Files in C order
+//! are read without moving any elements by reversing the strides.
+
+use std::{
+    fmt::Display,
+    io::{self, Read, Write},
+};
+
+use crate::{
+    array::{standard_stride, Allocator},
+    Array, ArrayView, Error,
+};
+
+const MAGIC: &[u8] = b"\x93NUMPY";
+/// The number of bytes that are read or written at once. This is a multiple of every element
+/// size.
+const BUFFER_LEN: usize = 8192;
+
+/// A primitive element type that can be stored in `.npy` files.
+pub trait NpyElement: Copy {
+    /// The NumPy type description of the element in little-endian byte order, like `<f8`.
+    const DESCR: &'static str;
+
+    /// Appends the little-endian bytes of the element to `out`.
+    fn write_bytes(self, out: &mut Vec<u8>);
+
+    /// Reads an element from exactly `size_of::<Self>()` bytes.
+    fn read_bytes(bytes: &[u8], little_endian: bool) -> Self;
+}
+
+macro_rules! impl_npy_element {
+    ($($t:ty = $descr:literal),+) => {
+        $(impl NpyElement for $t {
+            const DESCR: &'static str = $descr;
+
+            fn write_bytes(self, out: &mut Vec<u8>) {
+                out.extend_from_slice(&self.to_le_bytes());
+            }
+
+            fn read_bytes(bytes: &[u8], little_endian: bool) -> Self {
+                let bytes = bytes.try_into().unwrap();
+                if little_endian {
+                    <$t>::from_le_bytes(bytes)
+                } else {
+                    <$t>::from_be_bytes(bytes)
+                }
+            }
+        })+
+    };
+}
+
+impl_npy_element!(
+    u8 = "|u1",
+    u16 = "<u2",
+    u32 = "<u4",
+    u64 = "<u8",
+    i8 = "|i1",
+    i16 = "<i2",
+    i32 = "<i4",
+    i64 = "<i8",
+    f32 = "<f4",
+    f64 = "<f8"
+);
+
+impl NpyElement for bool {
+    const DESCR: &'static str = "|b1";
+
+    fn write_bytes(self, out: &mut Vec<u8>) {
+        out.push(self as u8);
+    }
+
+    fn read_bytes(bytes: &[u8], _little_endian: bool) -> Self {
+        bytes[0] != 0
+    }
+}
+
+/// The error type of reading `.npy` and `.npz` files.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum NpyError {
+    /// Reading from the underlying reader failed.
+    Io(io::Error),
+    /// The file is malformed or uses a feature that is not supported.
+    InvalidFormat(&'static str),
+    /// The elements of the file are not of the requested type.
+    DtypeMismatch { expected: &'static str, got: String },
+    /// The shape of the file can not be used for the requested array, e.g. because its number
+    /// of axes differs.
+    Array(Error),
+    /// An `.npz` file has no array of this name.
+    MissingEntry(String),
+}
+
+impl Display for NpyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NpyError::Io(e) => write!(f, "I/O error: {e}"),
+            NpyError::InvalidFormat(reason) => write!(f, "Invalid file: {reason}"),
+            NpyError::DtypeMismatch { expected, got } => {
+                write!(f, "Elements of type {got} can not be read as {expected}")
+            }
+            NpyError::Array(e) => e.fmt(f),
+            NpyError::MissingEntry(name) => write!(f, "Archive has no array named {name}"),
+        }
+    }
+}
+
+impl std::error::Error for NpyError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            NpyError::Io(e) => Some(e),
+            NpyError::Array(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for NpyError {
+    fn from(e: io::Error) -> Self {
+        NpyError::Io(e)
+    }
+}
+
+impl From<Error> for NpyError {
+    fn from(e: Error) -> Self {
+        NpyError::Array(e)
+    }
+}
+
+/// Finds the value of `key` in the Python dict literal of a header.
+fn header_value<'h>(header: &'h str, key: &str) -> Result<&'h str, NpyError> {
+    let start = header
+        .find(&format!("'{key}'"))
+        .ok_or(NpyError::InvalidFormat("header is missing a key"))?;
+    header[start + key.len() + 2..]
+        .trim_start()
+        .strip_prefix(':')
+        .map(str::trim_start)
+        .ok_or(NpyError::InvalidFormat("header is not a dict"))
+}
+
+/// Parses the header into the type description, whether the file is in Fortran order, and the
+/// shape.
+fn parse_header(header: &str) -> Result<(&str, bool, Vec<usize>), NpyError> {
+    let descr = header_value(header, "descr")?;
+    let descr = descr
+        .strip_prefix('\'')
+        .and_then(|d| d.split('\'').next())
+        .ok_or(NpyError::InvalidFormat("descr is not a string"))?;
+
+    let fortran_order = header_value(header, "fortran_order")?;
+    let fortran_order = if fortran_order.starts_with("True") {
+        true
+    } else if fortran_order.starts_with("False") {
+        false
+    } else {
+        return Err(NpyError::InvalidFormat("fortran_order is not a bool"));
+    };
+
+    let shape = header_value(header, "shape")?;
+    let shape = shape
+        .strip_prefix('(')
+        .and_then(|s| s.split(')').next())
+        .ok_or(NpyError::InvalidFormat("shape is not a tuple"))?
+        .split(',')
+        .map(str::trim)
+        .filter(|len| !len.is_empty())
+        .map(|len| len.trim_end_matches('L').parse())
+        .collect::<Result<Vec<usize>, _>>()
+        .map_err(|_| NpyError::InvalidFormat("shape is not a tuple of lengths"))?;
+
+    Ok((descr, fortran_order, shape))
+}
+
+/// Checks that `descr` describes `T` and returns whether it is little-endian.
+fn check_descr<T: NpyElement>(descr: &str) -> Result<bool, NpyError> {
+    let mismatch = || NpyError::DtypeMismatch {
+        expected: T::DESCR,
+        got: descr.to_owned(),
+    };
+    let (order, kind) = descr.split_at_checked(1).ok_or_else(mismatch)?;
+    if kind != &T::DESCR[1..] {
+        return Err(mismatch());
+    }
+    match order {
+        "<" | "|" => Ok(true),
+        ">" => Ok(false),
+        "=" => Ok(cfg!(target_endian = "little")),
+        _ => Err(mismatch()),
+    }
+}
+
+impl<T: NpyElement, const D: usize> Array<T, D> {
+    /// Reads an array from a `.npy` file. Returns an error if the elements of the file are not
+    /// of type `T` or if the file does not have `D` axes. Files in C order are not copied into
+    /// standard layout, the array just has reversed strides.
+    pub fn read_npy<R: Read>(mut reader: R) -> Result<Self, NpyError> {
+        let mut prefix = [0; 8];
+        reader.read_exact(&mut prefix)?;
+        if &prefix[..6] != MAGIC {
+            return Err(NpyError::InvalidFormat("not an npy file"));
+        }
+        let header_len = match prefix[6] {
+            1 => {
+                let mut len = [0; 2];
+                reader.read_exact(&mut len)?;
+                u16::from_le_bytes(len) as usize
+            }
+            2 | 3 => {
+                let mut len = [0; 4];
+                reader.read_exact(&mut len)?;
+                u32::from_le_bytes(len) as usize
+            }
+            _ => return Err(NpyError::InvalidFormat("unsupported npy version")),
+        };
+        // the length comes from the file, so memory only grows with data that is actually there
+        let mut header = Vec::new();
+        reader
+            .by_ref()
+            .take(header_len as u64)
+            .read_to_end(&mut header)?;
+        if header.len() != header_len {
+            return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into());
+        }
+        let header = std::str::from_utf8(&header)
+            .map_err(|_| NpyError::InvalidFormat("header is not text"))?;
+
+        let (descr, fortran_order, shape) = parse_header(header)?;
+        let little_endian = check_descr::<T>(descr)?;
+        let mut size: [usize; D] =
+            shape
+                .as_slice()
+                .try_into()
+                .map_err(|_| Error::RankMismatch {
+                    expected: D,
+                    got: shape.len(),
+                })?;
+        if !fortran_order {
+            size.reverse();
+        }
+        let (mut stride, len) = standard_stride(size)?;
+        if !fortran_order {
+            size.reverse();
+            stride.reverse();
+        }
+
+        let elem_size = size_of::<T>();
+        let mut remaining = len
+            .checked_mul(elem_size)
+            .filter(|&len| len <= isize::MAX as usize)
+            .ok_or(Error::SizeOverflow)?;
+        // the shape comes from the file, so memory only grows with data that is actually there
+        let mut data = Vec::new();
+        let mut buf = vec![0; remaining.min(BUFFER_LEN)];
+        while remaining > 0 {
+            let chunk = &mut buf[..remaining.min(BUFFER_LEN)];
+            reader.read_exact(chunk)?;
+            data.extend(
+                chunk
+                    .chunks_exact(elem_size)
+                    .map(|bytes| T::read_bytes(bytes, little_endian)),
+            );
+            remaining -= chunk.len();
+        }
+        Ok(Array::from_parts(size, stride, data))
+    }
+}
+
+/// Writes the magic string, version and header of an `.npy` file, padded to 64 bytes.
+fn write_header<W: Write, const D: usize>(
+    writer: &mut W,
+    descr: &str,
+    size: [usize; D],
+) -> io::Result<()> {
+    let mut shape = size.map(|len| len.to_string()).join(", ");
+    if D == 1 {
+        shape.push(',');
+    }
+    let mut header = format!("{{'descr': '{descr}', 'fortran_order': True, 'shape': ({shape}), }}");
+    // the data starts at a multiple of 64 bytes, after the prefix and a newline
+    let padded_len =
+        |prefix_len: usize| (prefix_len + header.len() + 1).div_ceil(64) * 64 - prefix_len;
+    // version 1 is understood by everyone, version 2 only has a longer length field
+    let (version, header_len) = match padded_len(10) {
+        len if len <= u16::MAX as usize => (1, len),
+        _ => (2, padded_len(12)),
+    };
+    header.extend(std::iter::repeat_n(' ', header_len - header.len() - 1));
+    header.push('\n');
+
+    writer.write_all(MAGIC)?;
+    writer.write_all(&[version, 0])?;
+    if version == 1 {
+        writer.write_all(&(header.len() as u16).to_le_bytes())?;
+    } else {
+        writer.write_all(&(header.len() as u32).to_le_bytes())?;
+    }
+    writer.write_all(header.as_bytes())
+}
+
+impl<T: NpyElement, const D: usize> ArrayView<'_, T, D> {
+    /// Writes the view to a `.npy` file in Fortran order, which keeps the x-first order of
+    /// this crate. Elements are always written in little-endian byte order.
+    pub fn write_npy<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        write_header(&mut writer, T::DESCR, self.size)?;
+        let mut buf = Vec::with_capacity(BUFFER_LEN);
+        for (_, x) in self.iter() {
+            x.write_bytes(&mut buf);
+            if buf.len() == BUFFER_LEN {
+                writer.write_all(&buf)?;
+                buf.clear();
+            }
+        }
+        writer.write_all(&buf)
+    }
+}
+
+impl<T: NpyElement, const D: usize, A: Allocator> Array<T, D, A> {
+    /// Writes the array to a `.npy` file, see [`ArrayView::write_npy`].
+    pub fn write_npy<W: Write>(&self, writer: W) -> io::Result<()> {
+        self.view().write_npy(writer)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Array, Error, NpyError};
+
+    #[test]
+    fn round_trip() {
+        let array = Array::new_by_enumeration([3, 2], |i| i as f64 / 2.0).transpose();
+        let mut file = Vec::new();
+        array.write_npy(&mut file).unwrap();
+        assert_eq!(file.len(), 128 + 6 * 8);
+        assert!(file.starts_with(
+            b"\x93NUMPY\x01\x00\x76\x00{'descr': '<f8', 'fortran_order': True, 'shape': (2, 3), }"
+        ));
+        let back = Array::<f64, 2>::read_npy(file.as_slice()).unwrap();
+        assert!(back.is_standard_layout());
+        assert_eq!(back.size(), [2, 3]);
+        assert_eq!(back[[1, 2]], array[[1, 2]]);
+
+        assert!(matches!(
+            Array::<f32, 2>::read_npy(file.as_slice()),
+            Err(NpyError::DtypeMismatch { expected: "<f4", got }) if got == "<f8"
+        ));
+        assert!(matches!(
+            Array::<f64, 3>::read_npy(file.as_slice()),
+            Err(NpyError::Array(Error::RankMismatch {
+                expected: 3,
+                got: 2
+            }))
+        ));
+    }
+
+    fn npy_file(header: &str, data: &[u8]) -> Vec<u8> {
+        let mut file = b"\x93NUMPY\x01\x00".to_vec();
+        file.extend_from_slice(&(header.len() as u16).to_le_bytes());
+        file.extend_from_slice(header.as_bytes());
+        file.extend_from_slice(data);
+        file
+    }
+
+    #[test]
+    fn c_order() {
+        let data = (0..6i16).flat_map(i16::to_be_bytes).collect::<Vec<_>>();
+        let file = npy_file(
+            "{'descr': '>i2', 'fortran_order': False, 'shape': (2, 3), }",
+            &data,
+        );
+        let array = Array::<i16, 2>::read_npy(file.as_slice()).unwrap();
+        assert_eq!(array.size(), [2, 3]);
+        assert_eq!(array.stride(), [3, 1]);
+        // C order has the last axis fastest
+        assert_eq!(array[[0, 1]], 1);
+        assert_eq!(array[[1, 0]], 3);
+    }
+
+    #[test]
+    fn untrusted_shape() {
+        let file = npy_file(
+            "{'descr': '<f8', 'fortran_order': True, 'shape': (1152921504606846976,), }",
+            &[0; 16],
+        );
+        assert!(matches!(
+            Array::<f64, 1>::read_npy(file.as_slice()),
+            Err(NpyError::Array(Error::SizeOverflow))
+        ));
+        let file = npy_file(
+            "{'descr': '|u1', 'fortran_order': True, 'shape': (1099511627776,), }",
+            &[0; 16],
+        );
+        assert!(matches!(
+            Array::<u8, 1>::read_npy(file.as_slice()),
+            Err(NpyError::Io(e)) if e.kind() == std::io::ErrorKind::UnexpectedEof
+        ));
+
+        let mut file = b"\x93NUMPY\x02\x00".to_vec();
+        file.extend(0xf0000000u32.to_le_bytes());
+        assert!(matches!(
+            Array::<u8, 1>::read_npy(file.as_slice()),
+            Err(NpyError::Io(e)) if e.kind() == std::io::ErrorKind::UnexpectedEof
+        ));
+    }
+}
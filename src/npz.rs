@@ -0,0 +1,312 @@
+//! Reading and writing NumPy's `.npz` archives, which are zip files of `.npy` files. Only
+//! uncompressed archives without zip64 extensions are supported, which is what `numpy.savez`
+//! writes for arrays smaller than 4 GiB.
+
+use std::io::{self, Read, Seek, SeekFrom, Write};
+
+use crate::{npy::NpyElement, Array, ArrayView, NpyError};
+
+const LOCAL_HEADER: u32 = 0x04034b50;
+const CENTRAL_HEADER: u32 = 0x02014b50;
+const END_OF_CENTRAL_DIRECTORY: u32 = 0x06054b50;
+/// January 1st 1980, the earliest date a zip file can store.
+const DOS_DATE: u16 = (1 << 5) | 1;
+
+const CRC_TABLE: [u32; 256] = {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 1 {
+                0xedb88320 ^ (crc >> 1)
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+/// The CRC-32 checksum that zip files use.
+fn crc32(bytes: &[u8]) -> u32 {
+    !bytes.iter().fold(!0, |crc, &b| {
+        CRC_TABLE[((crc ^ b as u32) & 0xff) as usize] ^ (crc >> 8)
+    })
+}
+
+/// A file of the archive, as stored in the central directory.
+struct Entry {
+    name: String,
+    crc: u32,
+    len: u32,
+    offset: u32,
+    compressed: bool,
+}
+
+fn u16_at(bytes: &[u8], at: usize) -> Result<u16, NpyError> {
+    bytes
+        .get(at..at + 2)
+        .map(|b| u16::from_le_bytes(b.try_into().unwrap()))
+        .ok_or(NpyError::InvalidFormat("zip file is truncated"))
+}
+
+fn u32_at(bytes: &[u8], at: usize) -> Result<u32, NpyError> {
+    bytes
+        .get(at..at + 4)
+        .map(|b| u32::from_le_bytes(b.try_into().unwrap()))
+        .ok_or(NpyError::InvalidFormat("zip file is truncated"))
+}
+
+/// Writes arrays into an uncompressed `.npz` archive. Call [`finish`](Self::finish) after the
+/// last array to write the directory of the archive.
+pub struct NpzWriter<W: Write> {
+    writer: W,
+    offset: u32,
+    entries: Vec<Entry>,
+}
+
+impl<W: Write> NpzWriter<W> {
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            offset: 0,
+            entries: Vec::new(),
+        }
+    }
+
+    /// Adds `array` to the archive as `name.npy`, so NumPy can load it as `name`.
+    pub fn add<T: NpyElement, const D: usize>(
+        &mut self,
+        name: &str,
+        array: ArrayView<'_, T, D>,
+    ) -> io::Result<()> {
+        let too_large = || io::Error::new(io::ErrorKind::InvalidInput, "npz archive exceeds 4 GiB");
+        let name = format!("{name}.npy");
+        let name_len = u16::try_from(name.len()).map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "array name exceeds 65535 bytes",
+            )
+        })?;
+        let mut data = Vec::new();
+        array.write_npy(&mut data)?;
+        let len = u32::try_from(data.len()).map_err(|_| too_large())?;
+        let entry = Entry {
+            crc: crc32(&data),
+            len,
+            offset: self.offset,
+            compressed: false,
+            name,
+        };
+
+        let mut header = Vec::with_capacity(30 + entry.name.len());
+        header.extend(LOCAL_HEADER.to_le_bytes());
+        // version needed, flags, method, time, date
+        for field in [20, 0, 0, 0, DOS_DATE] {
+            header.extend(u16::to_le_bytes(field));
+        }
+        for field in [entry.crc, len, len] {
+            header.extend(field.to_le_bytes());
+        }
+        header.extend(name_len.to_le_bytes());
+        header.extend(0u16.to_le_bytes());
+        header.extend(entry.name.as_bytes());
+        self.writer.write_all(&header)?;
+        self.writer.write_all(&data)?;
+
+        self.offset = (header.len() as u32)
+            .checked_add(len)
+            .and_then(|len| self.offset.checked_add(len))
+            .ok_or_else(too_large)?;
+        self.entries.push(entry);
+        Ok(())
+    }
+
+    /// Writes the directory of the archive and returns the underlying writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        let mut directory = Vec::new();
+        for entry in &self.entries {
+            directory.extend(CENTRAL_HEADER.to_le_bytes());
+            // version made by, version needed, flags, method, time, date
+            for field in [20, 20, 0, 0, 0, DOS_DATE] {
+                directory.extend(u16::to_le_bytes(field));
+            }
+            for field in [entry.crc, entry.len, entry.len] {
+                directory.extend(field.to_le_bytes());
+            }
+            // name length, extra length, comment length, disk, internal attributes
+            // add only writes names that fit into a u16
+            for field in [entry.name.len() as u16, 0, 0, 0, 0] {
+                directory.extend(field.to_le_bytes());
+            }
+            // external attributes, offset of the local header
+            for field in [0, entry.offset] {
+                directory.extend(u32::to_le_bytes(field));
+            }
+            directory.extend(entry.name.as_bytes());
+        }
+        let count = u16::try_from(self.entries.len())
+            .ok()
+            .filter(|&count| count != u16::MAX)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "too many arrays"))?;
+        let directory_len = directory.len() as u32;
+        directory.extend(END_OF_CENTRAL_DIRECTORY.to_le_bytes());
+        // disk, disk of the directory, entries on this disk, entries
+        for field in [0, 0, count, count] {
+            directory.extend(u16::to_le_bytes(field));
+        }
+        for field in [directory_len, self.offset] {
+            directory.extend(field.to_le_bytes());
+        }
+        // comment length
+        directory.extend(0u16.to_le_bytes());
+        self.writer.write_all(&directory)?;
+        Ok(self.writer)
+    }
+}
+
+/// Reads arrays from an `.npz` archive by name.
+pub struct NpzReader<R: Read + Seek> {
+    reader: R,
+    entries: Vec<Entry>,
+}
+
+impl<R: Read + Seek> NpzReader<R> {
+    /// Opens an archive by reading its directory.
+    pub fn new(mut reader: R) -> Result<Self, NpyError> {
+        // the end of the directory is followed by a comment of up to 65535 bytes
+        let file_len = reader.seek(SeekFrom::End(0))?;
+        let tail_len = file_len.min(22 + u16::MAX as u64);
+        reader.seek(SeekFrom::Start(file_len - tail_len))?;
+        let mut tail = vec![0; tail_len as usize];
+        reader.read_exact(&mut tail)?;
+        let end = (0..tail.len().saturating_sub(21))
+            .rev()
+            .find(|&i| tail[i..i + 4] == END_OF_CENTRAL_DIRECTORY.to_le_bytes())
+            .ok_or(NpyError::InvalidFormat("not a zip file"))?;
+        let count = u16_at(&tail, end + 10)?;
+        let directory_len = u32_at(&tail, end + 12)?;
+        let directory_offset = u32_at(&tail, end + 16)?;
+        if count == u16::MAX || directory_offset == u32::MAX {
+            return Err(NpyError::InvalidFormat("zip64 is not supported"));
+        }
+
+        if directory_offset as u64 + directory_len as u64 > file_len {
+            return Err(NpyError::InvalidFormat("zip file is truncated"));
+        }
+        reader.seek(SeekFrom::Start(directory_offset as u64))?;
+        let mut directory = vec![0; directory_len as usize];
+        reader.read_exact(&mut directory)?;
+        let mut entries = Vec::with_capacity(count as usize);
+        let mut at = 0;
+        for _ in 0..count {
+            if u32_at(&directory, at)? != CENTRAL_HEADER {
+                return Err(NpyError::InvalidFormat("zip directory is corrupted"));
+            }
+            let name_len = u16_at(&directory, at + 28)? as usize;
+            let name = directory
+                .get(at + 46..at + 46 + name_len)
+                .ok_or(NpyError::InvalidFormat("zip file is truncated"))?;
+            let entry = Entry {
+                name: String::from_utf8_lossy(name).into_owned(),
+                crc: u32_at(&directory, at + 16)?,
+                len: u32_at(&directory, at + 20)?,
+                offset: u32_at(&directory, at + 42)?,
+                compressed: u16_at(&directory, at + 10)? != 0,
+            };
+            if entry.len == u32::MAX || entry.offset == u32::MAX {
+                return Err(NpyError::InvalidFormat("zip64 is not supported"));
+            }
+            if entry.offset as u64 + entry.len as u64 > file_len {
+                return Err(NpyError::InvalidFormat("zip file is truncated"));
+            }
+            entries.push(entry);
+            at += 46
+                + name_len
+                + u16_at(&directory, at + 30)? as usize
+                + u16_at(&directory, at + 32)? as usize;
+        }
+        Ok(Self { reader, entries })
+    }
+
+    /// The names of all arrays in the archive, without the `.npy` extension.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.entries
+            .iter()
+            .map(|entry| entry.name.strip_suffix(".npy").unwrap_or(&entry.name))
+    }
+
+    /// Reads the array called `name`, see [`Array::read_npy`]. Returns an error if there is no
+    /// such array, if it is compressed, or if its checksum does not match.
+    pub fn read<T: NpyElement, const D: usize>(
+        &mut self,
+        name: &str,
+    ) -> Result<Array<T, D>, NpyError> {
+        let npy_name = format!("{name}.npy");
+        let entry = self
+            .entries
+            .iter()
+            .find(|entry| entry.name == npy_name || entry.name == name)
+            .ok_or_else(|| NpyError::MissingEntry(name.to_owned()))?;
+        if entry.compressed {
+            return Err(NpyError::InvalidFormat(
+                "compressed npz files are not supported",
+            ));
+        }
+
+        let mut header = [0; 30];
+        self.reader.seek(SeekFrom::Start(entry.offset as u64))?;
+        self.reader.read_exact(&mut header)?;
+        if u32_at(&header, 0)? != LOCAL_HEADER {
+            return Err(NpyError::InvalidFormat("zip entry is corrupted"));
+        }
+        let skip = u16_at(&header, 26)? as i64 + u16_at(&header, 28)? as i64;
+        self.reader.seek(SeekFrom::Current(skip))?;
+        let mut data = vec![0; entry.len as usize];
+        self.reader.read_exact(&mut data)?;
+        if crc32(&data) != entry.crc {
+            return Err(NpyError::InvalidFormat("zip entry has a wrong checksum"));
+        }
+        Array::read_npy(data.as_slice())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Cursor;
+
+    use super::crc32;
+    use crate::{Array, NpyError, NpzReader, NpzWriter};
+
+    #[test]
+    fn round_trip() {
+        assert_eq!(crc32(b"123456789"), 0xcbf43926);
+
+        let a = Array::new_by_enumeration([3, 2], |i| i as u8);
+        let b = Array::new_by_enumeration([2, 2, 2], |i| i % 3 == 0);
+        let mut writer = NpzWriter::new(Vec::new());
+        writer.add("a", a.view()).unwrap();
+        writer.add("b", b.view()).unwrap();
+        let file = writer.finish().unwrap();
+
+        let mut reader = NpzReader::new(Cursor::new(file)).unwrap();
+        assert_eq!(reader.names().collect::<Vec<_>>(), ["a", "b"]);
+        let b2 = reader.read::<bool, 3>("b").unwrap();
+        assert_eq!(b2.as_flattened(), b.as_flattened());
+        let a2 = reader.read::<u8, 2>("a.npy").unwrap();
+        assert_eq!(a2.as_flattened(), a.as_flattened());
+        assert!(matches!(
+            reader.read::<u8, 2>("c"),
+            Err(NpyError::MissingEntry(name)) if name == "c"
+        ));
+
+        let mut writer = NpzWriter::new(Vec::new());
+        let error = writer.add(&"a".repeat(65536), a.view()).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+    }
+}
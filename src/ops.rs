@@ -0,0 +1,313 @@
+//! Element-wise arithmetic operators for [`Array`]. Operations between two arrays broadcast
+//! them to a common size first (see [`broadcast_size`]), and operations with a single `T`
+//! apply it to every element. The operators panic if the sizes can not be broadcast, the
+//! `try_*` methods like [`Array::try_add`] return an error instead.
+
+use std::ops::{
+    Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign,
+};
+
+use crate::{
+    array::{standard_stride, Allocator},
+    Array, ArrayView, Error,
+};
+
+/// Computes the size two arrays are broadcast to when they are combined. Along every axis, the
+/// sizes must either be equal or one of them must be 1, in which case it is stretched to the
+/// other one. Returns the first incompatible axis otherwise.
+pub fn broadcast_size<const D: usize>(
+    left: [usize; D],
+    right: [usize; D],
+) -> Result<[usize; D], Error> {
+    let mut size = left;
+    for axis in 0..D {
+        if left[axis] == right[axis] || right[axis] == 1 {
+            continue;
+        }
+        if left[axis] != 1 {
+            return Err(Error::IncompatibleShapes {
+                axis,
+                left: left[axis],
+                right: right[axis],
+            });
+        }
+        size[axis] = right[axis];
+    }
+    Ok(size)
+}
+
+/// Stretches `array` to `size` by repeating its elements along every axis of length 1.
+fn expand<T: Clone, const D: usize, A: Allocator>(array: &mut Array<T, D, A>, size: [usize; D]) {
+    if array.size == size {
+        return;
+    }
+    let old = array.data.drain(..).collect::<Vec<_>>();
+    // SAFETY size and stride describe exactly the elements of old
+    let old_view = unsafe {
+        ArrayView::from_raw_parts(old.as_ptr(), array.size, array.stride.map(|x| x as isize))
+    };
+    let old_view = old_view
+        .broadcast_to(size)
+        .unwrap_or_else(|e| panic!("{e}"));
+    array.data.extend(old_view.iter().map(|(_, x)| x.clone()));
+    array.size = size;
+    array.stride = standard_stride(size).unwrap().0;
+}
+
+impl<'a, T, const D: usize> ArrayView<'a, T, D> {
+    /// Broadcasts the view and `other` to a common size (see [`broadcast_size`]) and combines
+    /// their elements with `f`, returning a new array in standard layout. Returns the first
+    /// incompatible axis if the sizes can not be broadcast.
+    pub fn try_zip_with<'b, U, V, F: FnMut(&'a T, &'b U) -> V>(
+        &self,
+        other: ArrayView<'b, U, D>,
+        mut f: F,
+    ) -> Result<Array<V, D>, Error> {
+        let size = broadcast_size(self.size, other.size)?;
+        let stride = standard_stride(size)?.0;
+        let data = self
+            .broadcast_to(size)?
+            .iter()
+            .zip(other.broadcast_to(size)?.iter())
+            .map(|((_, x), (_, y))| f(x, y))
+            .collect();
+        Ok(Array::from_parts(size, stride, data))
+    }
+}
+
+impl<T, const D: usize, A: Allocator> Array<T, D, A> {
+    /// Broadcasts the array and `other` to a common size and combines their elements with `f`,
+    /// see [`ArrayView::try_zip_with`].
+    pub fn try_zip_with<'a, 'b, U, V, F: FnMut(&'a T, &'b U) -> V>(
+        &'a self,
+        other: ArrayView<'b, U, D>,
+        f: F,
+    ) -> Result<Array<V, D>, Error> {
+        self.view().try_zip_with(other, f)
+    }
+}
+
+macro_rules! impl_op {
+    ($op:ident, $op_fn:ident, $try_fn:ident, $sym:literal, $op_assign:ident, $op_assign_fn:ident) => {
+        impl<T: Clone + $op<Output = T>, const D: usize, A: Allocator> Array<T, D, A> {
+            #[doc = concat!(
+                "Like `&self ", $sym, " &other`, but returns an error instead of panicking if ",
+                "the sizes can not be broadcast."
+            )]
+            pub fn $try_fn(&self, other: ArrayView<'_, T, D>) -> Result<Array<T, D>, Error> {
+                self.try_zip_with(other, |x, y| x.clone().$op_fn(y.clone()))
+            }
+        }
+
+        #[doc = concat!(
+            "Unlike `", $sym, "`, this keeps the size of `self` and only broadcasts `rhs` to ",
+            "it, so it panics if `rhs` is larger than `self` along any axis."
+        )]
+        impl<T: Clone + $op_assign, const D: usize, A: Allocator, B: Allocator>
+            $op_assign<&Array<T, D, B>> for Array<T, D, A>
+        {
+            fn $op_assign_fn(&mut self, rhs: &Array<T, D, B>) {
+                let rhs = rhs
+                    .broadcast_to(self.size)
+                    .unwrap_or_else(|e| panic!("{e}"));
+                self.iter_mut()
+                    .zip(rhs.iter())
+                    .for_each(|((_, x), (_, y))| x.$op_assign_fn(y.clone()));
+            }
+        }
+
+        /// Keeps the size of `self`, see the implementation for `&Array`.
+        impl<T: Clone + $op_assign, const D: usize, A: Allocator, B: Allocator>
+            $op_assign<Array<T, D, B>> for Array<T, D, A>
+        {
+            fn $op_assign_fn(&mut self, rhs: Array<T, D, B>) {
+                self.$op_assign_fn(&rhs);
+            }
+        }
+
+        impl<T: Clone + $op_assign, const D: usize, A: Allocator> $op_assign<T> for Array<T, D, A> {
+            fn $op_assign_fn(&mut self, rhs: T) {
+                self.data
+                    .iter_mut()
+                    .for_each(|x| x.$op_assign_fn(rhs.clone()));
+            }
+        }
+
+        impl<T: Clone + $op_assign, const D: usize, A: Allocator, B: Allocator> $op<&Array<T, D, B>>
+            for Array<T, D, A>
+        {
+            type Output = Array<T, D, A>;
+
+            fn $op_fn(mut self, rhs: &Array<T, D, B>) -> Self::Output {
+                let size = broadcast_size(self.size, rhs.size).unwrap_or_else(|e| panic!("{e}"));
+                expand(&mut self, size);
+                self.$op_assign_fn(rhs);
+                self
+            }
+        }
+
+        impl<T: Clone + $op_assign, const D: usize, A: Allocator, B: Allocator> $op<Array<T, D, B>>
+            for Array<T, D, A>
+        {
+            type Output = Array<T, D, A>;
+
+            fn $op_fn(self, rhs: Array<T, D, B>) -> Self::Output {
+                self.$op_fn(&rhs)
+            }
+        }
+
+        impl<T: Clone + $op<Output = T>, const D: usize, A: Allocator, B: Allocator>
+            $op<&Array<T, D, B>> for &Array<T, D, A>
+        {
+            type Output = Array<T, D>;
+
+            fn $op_fn(self, rhs: &Array<T, D, B>) -> Self::Output {
+                self.$try_fn(rhs.view()).unwrap_or_else(|e| panic!("{e}"))
+            }
+        }
+
+        impl<T: Clone + $op<Output = T>, const D: usize, A: Allocator, B: Allocator>
+            $op<Array<T, D, B>> for &Array<T, D, A>
+        {
+            type Output = Array<T, D>;
+
+            fn $op_fn(self, rhs: Array<T, D, B>) -> Self::Output {
+                self.$op_fn(&rhs)
+            }
+        }
+
+        impl<T: Clone + $op_assign, const D: usize, A: Allocator> $op<T> for Array<T, D, A> {
+            type Output = Array<T, D, A>;
+
+            fn $op_fn(mut self, rhs: T) -> Self::Output {
+                self.$op_assign_fn(rhs);
+                self
+            }
+        }
+
+        impl<T: Clone + $op<Output = T>, const D: usize, A: Allocator> $op<T> for &Array<T, D, A> {
+            type Output = Array<T, D>;
+
+            fn $op_fn(self, rhs: T) -> Self::Output {
+                let data = self
+                    .iter()
+                    .map(|(_, x)| x.clone().$op_fn(rhs.clone()))
+                    .collect();
+                Array::from_parts(self.size, standard_stride(self.size).unwrap().0, data)
+            }
+        }
+    };
+}
+
+impl_op!(Add, add, try_add, "+", AddAssign, add_assign);
+impl_op!(Sub, sub, try_sub, "-", SubAssign, sub_assign);
+impl_op!(Mul, mul, try_mul, "*", MulAssign, mul_assign);
+impl_op!(Div, div, try_div, "/", DivAssign, div_assign);
+impl_op!(Rem, rem, try_rem, "%", RemAssign, rem_assign);
+
+impl<T: Clone + Neg<Output = T>, const D: usize, A: Allocator> Neg for Array<T, D, A> {
+    type Output = Array<T, D, A>;
+
+    fn neg(mut self) -> Self::Output {
+        self.data.iter_mut().for_each(|x| *x = -x.clone());
+        self
+    }
+}
+
+impl<T: Clone + Neg<Output = T>, const D: usize, A: Allocator> Neg for &Array<T, D, A> {
+    type Output = Array<T, D>;
+
+    fn neg(self) -> Self::Output {
+        let data = self.iter().map(|(_, x)| -x.clone()).collect();
+        Array::from_parts(self.size, standard_stride(self.size).unwrap().0, data)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Array, Error};
+
+    #[test]
+    fn arithmetic() {
+        let a = Array::new_by_enumeration([3, 2], |i| i as i32);
+        let b = Array::new_with([3, 2], 2);
+        assert_eq!((&a + &b).as_flattened(), &[2, 3, 4, 5, 6, 7]);
+        assert_eq!((&a * 3).as_flattened(), &[0, 3, 6, 9, 12, 15]);
+        assert_eq!((a.clone() % b.clone()).as_flattened(), &[0, 1, 0, 1, 0, 1]);
+        assert_eq!((-&a).as_flattened(), &[0, -1, -2, -3, -4, -5]);
+
+        let mut c = a.clone().transpose();
+        c -= Array::new_with([2, 3], 1);
+        assert_eq!(c[[1, 2]], 4);
+        c /= 2;
+        assert_eq!(c[[1, 2]], 2);
+        // the result of a borrowed operation is always in standard layout
+        assert_eq!((&c + 0).as_flattened(), &[0, 1, 0, 1, 0, 2]);
+    }
+
+    #[test]
+    fn broadcasting() {
+        let image = Array::new_by_enumeration([2, 2, 3], |i| i as f32);
+        let pixel = Array::from_flat(vec![1.0, 0.5, 0.0], [1, 1, 3]).unwrap();
+        let tinted = &image * &pixel;
+        assert_eq!(tinted.size(), [2, 2, 3]);
+        assert_eq!(tinted[[1, 1, 0]], 3.0);
+        assert_eq!(tinted[[1, 1, 1]], 3.5);
+        assert_eq!(tinted[[1, 1, 2]], 0.0);
+
+        let column = Array::new_by_enumeration([3, 1], |i| i);
+        let row = Array::new_by_enumeration([1, 2], |i| i * 10);
+        let grid = column + row;
+        assert_eq!(grid.as_flattened(), &[0, 1, 2, 10, 11, 12]);
+
+        let mut grid = grid;
+        grid += Array::new_with([1, 1], 1);
+        assert_eq!(grid[[2, 1]], 13);
+    }
+
+    #[test]
+    fn fallible() {
+        let a = Array::new_by_enumeration([3, 2], |i| i);
+        let b = Array::new_with([2, 3], 1);
+        assert_eq!(
+            a.try_sub(b.view()).err(),
+            Some(Error::IncompatibleShapes {
+                axis: 0,
+                left: 3,
+                right: 2
+            })
+        );
+        let column = Array::new_with([1, 2], 10);
+        assert_eq!(
+            a.try_mul(column.view()).unwrap().as_flattened(),
+            &[0, 10, 20, 30, 40, 50]
+        );
+        let pairs = a.try_zip_with(column.view(), |x, y| (*x, *y)).unwrap();
+        assert_eq!(pairs[[2, 1]], (5, 10));
+    }
+
+    #[test]
+    fn compound_assignment() {
+        let mut a = Array::new_by_enumeration([3, 2], |i| i);
+        a += &Array::new_by_enumeration([3, 1], |i| i * 10);
+        assert_eq!(a.as_flattened(), &[0, 11, 22, 3, 14, 25]);
+        a *= Array::new_with([1, 2], 2);
+        assert_eq!(a.as_flattened(), &[0, 22, 44, 6, 28, 50]);
+        a %= 7;
+        assert_eq!(a.as_flattened(), &[0, 1, 2, 6, 0, 1]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn compound_assignment_does_not_grow() {
+        // `&a + &b` would broadcast both to [3, 2], but `+=` keeps the size of `a`
+        let mut a = Array::new_with([1, 2], 0);
+        a += &Array::new_with([3, 2], 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn size_mismatch() {
+        let _ = &Array::new_with([3, 2], 0) + &Array::new_with([2, 3], 0);
+    }
+}
@@ -0,0 +1,191 @@
+use rayon::prelude::*;
+
+use crate::{
+    array::{standard_stride, Allocator},
+    chunks::{chunk_grid, chunk_size},
+    iterator::locate,
+    lanes::lane_parts,
+    Array, ArrayView, ArrayViewMut,
+};
+
+/// Pointer to the elements of an array that is handed out to rayon's worker threads.
+struct SendPtr<T>(*mut T);
+
+impl<T> Clone for SendPtr<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for SendPtr<T> {}
+
+unsafe impl<T: Send> Send for SendPtr<T> {}
+unsafe impl<T: Send> Sync for SendPtr<T> {}
+
+impl<T> SendPtr<T> {
+    // closures must capture the whole struct, not just the raw pointer inside of it
+    fn get(self) -> *mut T {
+        self.0
+    }
+}
+
+impl<T, const D: usize, A: Allocator> Array<T, D, A> {
+    /// Parallel version of [`iter`](Self::iter). Items are yielded in the same x-first order
+    /// when collected.
+    pub fn par_iter(&self) -> impl IndexedParallelIterator<Item = ([usize; D], &T)>
+    where
+        T: Sync,
+    {
+        let view = self.view();
+        (0..self.data.len()).into_par_iter().map(move |i| {
+            // capture the whole view, its raw pointer alone is not Send
+            let view = view;
+            let (loc, offset) = locate(view.size, view.stride, i);
+            // SAFETY locate only yields offsets of valid locations
+            (loc, unsafe { &*view.ptr.offset(offset) })
+        })
+    }
+
+    /// Parallel version of [`iter_mut`](Self::iter_mut). Items are yielded in the same x-first
+    /// order when collected.
+    pub fn par_iter_mut(&mut self) -> impl IndexedParallelIterator<Item = ([usize; D], &mut T)>
+    where
+        T: Send,
+    {
+        let view = self.view_mut();
+        let (size, stride) = (view.size, view.stride);
+        let ptr = SendPtr(view.ptr);
+        (0..self.data.len()).into_par_iter().map(move |i| {
+            let (loc, offset) = locate(size, stride, i);
+            // SAFETY locate yields offsets of valid locations, and each of them only once
+            (loc, unsafe { &mut *ptr.get().offset(offset) })
+        })
+    }
+
+    /// Applies `f` to every element in parallel, returning a new array in standard layout.
+    pub fn par_map<B: Send, F: Fn(&T) -> B + Sync + Send>(&self, f: F) -> Array<B, D>
+    where
+        T: Sync,
+    {
+        let data = self.par_iter().map(|(_, x)| f(x)).collect();
+        Array::from_parts(self.size, standard_stride(self.size).unwrap().0, data)
+    }
+
+    /// Parallel version of [`lanes`](Self::lanes). Panics if `axis` is not an axis of the
+    /// array.
+    pub fn par_lanes(&self, axis: usize) -> impl IndexedParallelIterator<Item = ArrayView<'_, T, 1>>
+    where
+        T: Sync,
+    {
+        let view = self.view();
+        let (cursor, len, lane_stride) = lane_parts(view.size, view.stride, axis);
+        let (grid, stride) = (cursor.size, cursor.stride);
+        (0..grid.iter().product()).into_par_iter().map(move |i| {
+            // capture the whole view, its raw pointer alone is not Send
+            let view = view;
+            let (_, offset) = locate(grid, stride, i);
+            // SAFETY the lane starts at a valid location and runs along the array
+            unsafe { ArrayView::from_raw_parts(view.ptr.offset(offset), [len], [lane_stride]) }
+        })
+    }
+
+    /// Parallel version of [`chunks`](Self::chunks). Panics if any length of `size` is 0.
+    pub fn par_chunks(
+        &self,
+        size: [usize; D],
+    ) -> impl IndexedParallelIterator<Item = ArrayView<'_, T, D>>
+    where
+        T: Sync,
+    {
+        let view = self.view();
+        let (grid, grid_stride) = chunk_grid(view.size, view.stride, size, false);
+        (0..grid.iter().product()).into_par_iter().map(move |i| {
+            // capture the whole view, its raw pointer alone is not Send
+            let view = view;
+            let (loc, offset) = locate(grid, grid_stride, i);
+            // SAFETY the chunk is cut to fit into the array
+            unsafe {
+                ArrayView::from_raw_parts(
+                    view.ptr.offset(offset),
+                    chunk_size(view.size, size, loc),
+                    view.stride,
+                )
+            }
+        })
+    }
+
+    /// Parallel version of [`chunks_mut`](Self::chunks_mut). Panics if any length of `size` is
+    /// 0.
+    pub fn par_chunks_mut(
+        &mut self,
+        size: [usize; D],
+    ) -> impl IndexedParallelIterator<Item = ArrayViewMut<'_, T, D>>
+    where
+        T: Send,
+    {
+        let view = self.view_mut();
+        let (view_size, stride) = (view.size, view.stride);
+        let ptr = SendPtr(view.ptr);
+        let (grid, grid_stride) = chunk_grid(view_size, stride, size, false);
+        (0..grid.iter().product()).into_par_iter().map(move |i| {
+            let (loc, offset) = locate(grid, grid_stride, i);
+            // SAFETY the chunk is cut to fit into the array, and chunks never overlap
+            unsafe {
+                ArrayViewMut::from_raw_parts(
+                    ptr.get().offset(offset),
+                    chunk_size(view_size, size, loc),
+                    stride,
+                )
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rayon::prelude::*;
+
+    use crate::Array;
+
+    #[test]
+    fn par_iter() {
+        let mut array = Array::new_by_enumeration([4, 3, 2], |i| i).permuted_axes([1, 2, 0]);
+        let serial = array.iter().collect::<Vec<_>>();
+        let parallel = array.par_iter().collect::<Vec<_>>();
+        assert_eq!(serial, parallel);
+
+        array.par_iter_mut().for_each(|(loc, x)| *x = loc[0]);
+        assert_eq!(array[[2, 1, 3]], 2);
+        let doubled = array.par_map(|x| x * 2);
+        assert_eq!(doubled.size(), [3, 2, 4]);
+        assert_eq!(doubled[[2, 0, 0]], 4);
+    }
+
+    #[test]
+    fn par_lanes_and_chunks() {
+        let mut array = Array::new_by_enumeration([5, 3], |i| i);
+        let sums = array
+            .par_lanes(0)
+            .map(|lane| lane.sum())
+            .collect::<Vec<_>>();
+        assert_eq!(sums, vec![10, 35, 60]);
+        let empty = Array::<u32, 2>::new([3, 0]);
+        assert_eq!(empty.par_lanes(1).map(|lane| lane.sum()).sum::<u32>(), 0);
+
+        let sizes = array
+            .par_chunks([2, 2])
+            .map(|chunk| chunk.size())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            sizes,
+            array.chunks([2, 2]).map(|c| c.size()).collect::<Vec<_>>()
+        );
+
+        array
+            .par_chunks_mut([2, 2])
+            .enumerate()
+            .for_each(|(i, mut chunk)| chunk.iter_mut().for_each(|(_, x)| *x = i));
+        assert_eq!(array[[4, 2]], 5);
+        assert_eq!(array[[3, 1]], 1);
+    }
+}
@@ -0,0 +1,340 @@
+//! A compact native format for arrays of plain data. The file starts with a 16 byte header of
+//! the magic string `MNDA`, the byte order of the elements, a version, the element size and the
+//! number of axes, followed by the length of every axis as a `u64` and the bytes of all elements
+//! in x-first order. Header fields are little-endian, elements are stored in native byte order.
+//! The elements start at a multiple of 8 bytes, so [`ArrayView::from_bytes`] can use a loaded
+//! file without copying it.
+
+use std::{
+    fmt::Display,
+    io::{self, Read, Write},
+};
+
+use crate::{
+    array::{standard_stride, Allocator},
+    Array, ArrayView, Error,
+};
+
+const MAGIC: &[u8] = b"MNDA";
+const VERSION: u8 = 1;
+/// The number of bytes that are read at once.
+const BUFFER_LEN: usize = 1 << 16;
+const NATIVE_ORDER: u8 = if cfg!(target_endian = "little") {
+    b'<'
+} else {
+    b'>'
+};
+
+/// Types that are plain bytes, so they can be written and read without conversion.
+///
+/// # Safety
+/// Every bit pattern must be a valid value of the type, and the type must not contain padding
+/// bytes or pointers.
+pub unsafe trait Pod: Copy + 'static {}
+
+macro_rules! impl_pod {
+    ($($t:ty),+) => {
+        $(unsafe impl Pod for $t {})+
+    };
+}
+
+impl_pod!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+
+// SAFETY arrays have no padding between their elements
+unsafe impl<T: Pod, const N: usize> Pod for [T; N] {}
+
+fn as_bytes<T: Pod>(data: &[T]) -> &[u8] {
+    // SAFETY Pod types have no padding, so all of their bytes are initialized
+    unsafe { std::slice::from_raw_parts(data.as_ptr().cast(), size_of_val(data)) }
+}
+
+fn as_bytes_mut<T: Pod>(data: &mut [T]) -> &mut [u8] {
+    // SAFETY Pod types accept any bytes
+    unsafe { std::slice::from_raw_parts_mut(data.as_mut_ptr().cast(), size_of_val(data)) }
+}
+
+/// The error type of reading raw arrays.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum RawError {
+    /// The reader returned an error.
+    Io(io::Error),
+    /// The data is not a raw array or is truncated.
+    InvalidFormat(&'static str),
+    /// The elements were written with a different size than the requested type has.
+    ElementSizeMismatch { expected: usize, got: usize },
+    /// The elements were written on a machine with a different byte order.
+    EndiannessMismatch,
+    /// The elements are not aligned for the requested type, so they can not be viewed without
+    /// copying them.
+    Misaligned,
+    /// The header can not be used for an array of the requested type, e.g. because its number
+    /// of axes differs.
+    Array(Error),
+}
+
+impl Display for RawError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RawError::Io(e) => write!(f, "I/O error: {e}"),
+            RawError::InvalidFormat(reason) => write!(f, "Invalid raw array: {reason}"),
+            RawError::ElementSizeMismatch { expected, got } => write!(
+                f,
+                "Elements of {got} bytes can not be read as elements of {expected} bytes"
+            ),
+            RawError::EndiannessMismatch => {
+                write!(f, "Elements were written in a different byte order")
+            }
+            RawError::Misaligned => write!(f, "Elements are not aligned for the element type"),
+            RawError::Array(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for RawError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            RawError::Io(e) => Some(e),
+            RawError::Array(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for RawError {
+    fn from(e: io::Error) -> Self {
+        RawError::Io(e)
+    }
+}
+
+impl From<Error> for RawError {
+    fn from(e: Error) -> Self {
+        RawError::Array(e)
+    }
+}
+
+/// Checks the first 16 bytes of the header against `T` and `D`.
+fn check_prefix<T: Pod, const D: usize>(prefix: &[u8]) -> Result<(), RawError> {
+    if &prefix[..4] != MAGIC {
+        return Err(RawError::InvalidFormat("magic number is missing"));
+    }
+    if prefix[5] != VERSION {
+        return Err(RawError::InvalidFormat("unsupported version"));
+    }
+    match prefix[4] {
+        order if order == NATIVE_ORDER => (),
+        b'<' | b'>' => return Err(RawError::EndiannessMismatch),
+        _ => return Err(RawError::InvalidFormat("unknown byte order")),
+    }
+    let elem_size = u32::from_le_bytes(prefix[8..12].try_into().unwrap()) as usize;
+    if elem_size != size_of::<T>() {
+        return Err(RawError::ElementSizeMismatch {
+            expected: size_of::<T>(),
+            got: elem_size,
+        });
+    }
+    let dimensions = u32::from_le_bytes(prefix[12..16].try_into().unwrap()) as usize;
+    if dimensions != D {
+        return Err(Error::RankMismatch {
+            expected: D,
+            got: dimensions,
+        }
+        .into());
+    }
+    Ok(())
+}
+
+/// Reads the lengths of the axes after the prefix.
+fn parse_size<const D: usize>(bytes: &[u8]) -> Result<[usize; D], RawError> {
+    let mut size = [0; D];
+    for (len, bytes) in size.iter_mut().zip(bytes.chunks_exact(8)) {
+        *len = u64::from_le_bytes(bytes.try_into().unwrap())
+            .try_into()
+            .map_err(|_| Error::SizeOverflow)?;
+    }
+    Ok(size)
+}
+
+impl<T: Pod, const D: usize> Array<T, D> {
+    /// Reads an array that was written with [`write_raw`](Self::write_raw). Returns an error if
+    /// it was written with a different element size, number of axes or byte order.
+    pub fn read_raw<R: Read>(mut reader: R) -> Result<Self, RawError> {
+        let mut prefix = [0; 16];
+        reader.read_exact(&mut prefix)?;
+        check_prefix::<T, D>(&prefix)?;
+        let mut size = vec![0; 8 * D];
+        reader.read_exact(&mut size)?;
+        let size = parse_size(&size)?;
+        let (stride, len) = standard_stride(size)?;
+        len.checked_mul(size_of::<T>())
+            .filter(|&len| len <= isize::MAX as usize)
+            .ok_or(Error::SizeOverflow)?;
+
+        // the size comes from the file, so memory only grows with data that is actually there
+        // zero-sized elements take up neither memory nor bytes of the file, so they are read at
+        // once
+        let chunk_len = match size_of::<T>() {
+            0 => len,
+            elem_size => (BUFFER_LEN / elem_size).max(1),
+        };
+        let mut data: Vec<T> = Vec::new();
+        while data.len() < len {
+            let start = data.len();
+            let end = len.min(start + chunk_len);
+            data.reserve(end - start);
+            // SAFETY zeroes are a valid Pod value, and the capacity is at least end
+            unsafe {
+                std::ptr::write_bytes(data.as_mut_ptr().add(start), 0, end - start);
+                data.set_len(end);
+            }
+            reader.read_exact(as_bytes_mut(&mut data[start..]))?;
+        }
+        Ok(Array::from_parts(size, stride, data))
+    }
+}
+
+impl<'a, T: Pod, const D: usize> ArrayView<'a, T, D> {
+    /// Views the elements of a raw array in `bytes` without copying them. Returns
+    /// [`RawError::Misaligned`] if the elements are not aligned for `T`, in which case
+    /// [`Array::read_raw`] can read a copy instead.
+    pub fn from_bytes(bytes: &'a [u8]) -> Result<Self, RawError> {
+        let truncated = || RawError::InvalidFormat("data is truncated");
+        let prefix = bytes.get(..16).ok_or_else(truncated)?;
+        check_prefix::<T, D>(prefix)?;
+        let size = parse_size(bytes.get(16..16 + 8 * D).ok_or_else(truncated)?)?;
+        let (stride, len) = standard_stride(size)?;
+        let data_len = len.checked_mul(size_of::<T>()).ok_or(Error::SizeOverflow)?;
+        let data = bytes
+            .get(16 + 8 * D..)
+            .and_then(|data| data.get(..data_len))
+            .ok_or_else(truncated)?;
+        if !data.as_ptr().cast::<T>().is_aligned() {
+            return Err(RawError::Misaligned);
+        }
+        // SAFETY the bytes are aligned, hold len elements, and any bytes are valid for Pod
+        Ok(unsafe {
+            ArrayView::from_raw_parts(data.as_ptr().cast(), size, stride.map(|x| x as isize))
+        })
+    }
+
+    /// Writes a header with the element size, byte order and size of the view, followed by the
+    /// bytes of its elements in x-first order. Views in standard layout are written at once,
+    /// others element by element.
+    pub fn write_raw<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        let mut header = Vec::with_capacity(16 + 8 * D);
+        header.extend(MAGIC);
+        header.extend([NATIVE_ORDER, VERSION, 0, 0]);
+        header.extend((size_of::<T>() as u32).to_le_bytes());
+        header.extend((D as u32).to_le_bytes());
+        for len in self.size {
+            header.extend((len as u64).to_le_bytes());
+        }
+        writer.write_all(&header)?;
+
+        let (stride, len) = standard_stride(self.size).unwrap();
+        if self.stride == stride.map(|x| x as isize) {
+            // SAFETY the view is contiguous in x-first order
+            let data = unsafe { std::slice::from_raw_parts(self.ptr, len) };
+            return writer.write_all(as_bytes(data));
+        }
+        for (_, x) in self.iter() {
+            writer.write_all(as_bytes(std::slice::from_ref(x)))?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: Pod, const D: usize, A: Allocator> Array<T, D, A> {
+    /// Writes the array in the raw format, see [`ArrayView::write_raw`].
+    pub fn write_raw<W: Write>(&self, writer: W) -> io::Result<()> {
+        self.view().write_raw(writer)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Array, ArrayView, Error, RawError};
+
+    #[test]
+    fn round_trip() {
+        let table = Array::new_by_enumeration([4, 3, 2], |i| [i as f32, -(i as f32)]);
+        let mut file = Vec::new();
+        table.write_raw(&mut file).unwrap();
+        assert_eq!(file.len(), 16 + 3 * 8 + 24 * 8);
+        let back = Array::<[f32; 2], 3>::read_raw(file.as_slice()).unwrap();
+        assert_eq!(back.as_flattened(), table.as_flattened());
+
+        let mut transposed = Vec::new();
+        table
+            .clone()
+            .permuted_axes([2, 1, 0])
+            .write_raw(&mut transposed)
+            .unwrap();
+        let back = Array::<[f32; 2], 3>::read_raw(transposed.as_slice()).unwrap();
+        assert_eq!(back.size(), [2, 3, 4]);
+        assert_eq!(back[[1, 2, 3]], table[[3, 2, 1]]);
+
+        assert!(matches!(
+            Array::<f32, 3>::read_raw(file.as_slice()),
+            Err(RawError::ElementSizeMismatch {
+                expected: 4,
+                got: 8
+            })
+        ));
+        assert!(matches!(
+            Array::<[f32; 2], 2>::read_raw(file.as_slice()),
+            Err(RawError::Array(Error::RankMismatch {
+                expected: 2,
+                got: 3
+            }))
+        ));
+    }
+
+    #[test]
+    fn untrusted_size() {
+        let mut file = Vec::new();
+        Array::new_with([4], 0u64).write_raw(&mut file).unwrap();
+        file[16..24].copy_from_slice(&(1u64 << 60).to_le_bytes());
+        assert!(matches!(
+            Array::<u64, 1>::read_raw(file.as_slice()),
+            Err(RawError::Array(Error::SizeOverflow))
+        ));
+        file[16..24].copy_from_slice(&(1u64 << 40).to_le_bytes());
+        assert!(matches!(
+            Array::<u64, 1>::read_raw(file.as_slice()),
+            Err(RawError::Io(e)) if e.kind() == std::io::ErrorKind::UnexpectedEof
+        ));
+
+        let mut file = Vec::new();
+        Array::new_with([4], [0u8; 0]).write_raw(&mut file).unwrap();
+        file[16..24].copy_from_slice(&(1u64 << 62).to_le_bytes());
+        let array = Array::<[u8; 0], 1>::read_raw(file.as_slice()).unwrap();
+        assert_eq!(array.size(), [1 << 62]);
+    }
+
+    #[test]
+    fn from_bytes() {
+        let table = Array::new_by_enumeration([3, 2], |i| i as u32);
+        let mut file = Vec::new();
+        table.write_raw(&mut file).unwrap();
+        // u64s are aligned for u32, and a byte more is not
+        let mut aligned = vec![0u64; file.len().div_ceil(8) + 1];
+        let bytes = unsafe {
+            std::slice::from_raw_parts_mut(aligned.as_mut_ptr().cast::<u8>(), aligned.len() * 8)
+        };
+        bytes[..file.len()].copy_from_slice(&file);
+        let view = ArrayView::<u32, 2>::from_bytes(bytes).unwrap();
+        assert_eq!(view[[1, 1]], 4);
+        assert!(std::ptr::eq(&view[[0, 0]], bytes[32..].as_ptr().cast()));
+
+        bytes.copy_within(..file.len(), 1);
+        assert!(matches!(
+            ArrayView::<u32, 2>::from_bytes(&bytes[1..]),
+            Err(RawError::Misaligned)
+        ));
+        assert!(matches!(
+            ArrayView::<u32, 2>::from_bytes(&file[..file.len() - 1]),
+            Err(RawError::InvalidFormat(_))
+        ));
+    }
+}
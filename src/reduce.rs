@@ -0,0 +1,486 @@
+//! Reductions over the whole array and along single axes. Axis reductions keep the reduced axis
+//! with a length of 1, so the result has the same dimensionality as the input. Use
+//! [`Array::remove_axis`] to get rid of it.
+
+use std::{
+    cmp::Ordering,
+    iter::{Product, Sum},
+};
+
+use crate::{
+    array::{standard_stride, Allocator},
+    Array, ArrayView,
+};
+
+impl<'a, T, const D: usize> ArrayView<'a, T, D> {
+    /// Calls `f` with a view of every 1D lane along `axis` and collects the results into an
+    /// array whose `axis` has length 1. This is the building block of all axis reductions, and
+    /// can be used for custom ones like the median.
+    pub fn map_axis<B, F: FnMut(ArrayView<'a, T, 1>) -> B>(
+        &self,
+        axis: usize,
+        f: F,
+    ) -> Array<B, D> {
+        let data = self.lanes(axis).map(f).collect();
+        let mut size = self.size;
+        size[axis] = 1;
+        Array::from_parts(size, standard_stride(size).unwrap().0, data)
+    }
+
+    /// Folds every 1D lane along `axis` into a single value, starting with `init`. The result
+    /// has a length of 1 along `axis`.
+    pub fn fold_axis<B: Clone, F: FnMut(B, &'a T) -> B>(
+        &self,
+        axis: usize,
+        init: B,
+        mut f: F,
+    ) -> Array<B, D> {
+        self.map_axis(axis, |lane| {
+            lane.iter().fold(init.clone(), |acc, (_, x)| f(acc, x))
+        })
+    }
+
+    /// The smallest element, ignoring elements that are not comparable to themselves (NaN).
+    pub fn min(&self) -> Option<&'a T>
+    where
+        T: PartialOrd,
+    {
+        self.iter()
+            .map(|(_, x)| x)
+            .filter(|x| x.partial_cmp(x).is_some())
+            .reduce(|min, x| if x < min { x } else { min })
+    }
+
+    /// The largest element, ignoring elements that are not comparable to themselves (NaN).
+    pub fn max(&self) -> Option<&'a T>
+    where
+        T: PartialOrd,
+    {
+        self.iter()
+            .map(|(_, x)| x)
+            .filter(|x| x.partial_cmp(x).is_some())
+            .reduce(|max, x| if x > max { x } else { max })
+    }
+
+    /// The location of the first element for which `compare` returns
+    /// [`Less`](Ordering::Less) against every element before it.
+    pub fn argmin_by<F: FnMut(&T, &T) -> Ordering>(&self, mut compare: F) -> Option<[usize; D]> {
+        self.iter()
+            .reduce(|min, x| {
+                if compare(x.1, min.1) == Ordering::Less {
+                    x
+                } else {
+                    min
+                }
+            })
+            .map(|(loc, _)| loc)
+    }
+
+    /// The location of the first element for which `compare` returns
+    /// [`Greater`](Ordering::Greater) against every element before it.
+    pub fn argmax_by<F: FnMut(&T, &T) -> Ordering>(&self, mut compare: F) -> Option<[usize; D]> {
+        self.iter()
+            .reduce(|max, x| {
+                if compare(x.1, max.1) == Ordering::Greater {
+                    x
+                } else {
+                    max
+                }
+            })
+            .map(|(loc, _)| loc)
+    }
+
+    /// The location of the first element with the smallest key.
+    pub fn argmin_by_key<K: Ord, F: FnMut(&T) -> K>(&self, mut f: F) -> Option<[usize; D]> {
+        self.argmin_by(|a, b| f(a).cmp(&f(b)))
+    }
+
+    /// The location of the first element with the largest key.
+    pub fn argmax_by_key<K: Ord, F: FnMut(&T) -> K>(&self, mut f: F) -> Option<[usize; D]> {
+        self.argmax_by(|a, b| f(a).cmp(&f(b)))
+    }
+
+    /// The location of the first smallest element, ignoring elements that are not comparable to
+    /// themselves (NaN).
+    pub fn argmin(&self) -> Option<[usize; D]>
+    where
+        T: PartialOrd,
+    {
+        self.iter()
+            .filter(|(_, x)| x.partial_cmp(x).is_some())
+            .reduce(|min, x| if x.1 < min.1 { x } else { min })
+            .map(|(loc, _)| loc)
+    }
+
+    /// The location of the first largest element, ignoring elements that are not comparable to
+    /// themselves (NaN).
+    pub fn argmax(&self) -> Option<[usize; D]>
+    where
+        T: PartialOrd,
+    {
+        self.iter()
+            .filter(|(_, x)| x.partial_cmp(x).is_some())
+            .reduce(|max, x| if x.1 > max.1 { x } else { max })
+            .map(|(loc, _)| loc)
+    }
+
+    /// The index along `axis` of the smallest element of every lane along `axis`. The result
+    /// has a length of 1 along `axis`, and contains `None` for lanes without comparable elements.
+    pub fn argmin_axis(&self, axis: usize) -> Array<Option<usize>, D>
+    where
+        T: PartialOrd,
+    {
+        self.map_axis(axis, |lane| lane.argmin().map(|[i]| i))
+    }
+
+    /// The index along `axis` of the largest element of every lane along `axis`. The result
+    /// has a length of 1 along `axis`, and contains `None` for lanes without comparable elements.
+    pub fn argmax_axis(&self, axis: usize) -> Array<Option<usize>, D>
+    where
+        T: PartialOrd,
+    {
+        self.map_axis(axis, |lane| lane.argmax().map(|[i]| i))
+    }
+}
+
+impl<T: Clone, const D: usize> ArrayView<'_, T, D> {
+    pub fn sum(&self) -> T
+    where
+        T: Sum,
+    {
+        self.iter().map(|(_, x)| x.clone()).sum()
+    }
+
+    pub fn product(&self) -> T
+    where
+        T: Product,
+    {
+        self.iter().map(|(_, x)| x.clone()).product()
+    }
+
+    /// Sums up the elements along `axis`. The result has a length of 1 along `axis`.
+    pub fn sum_axis(&self, axis: usize) -> Array<T, D>
+    where
+        T: Sum,
+    {
+        self.map_axis(axis, |lane| lane.sum())
+    }
+
+    /// Multiplies the elements along `axis`. The result has a length of 1 along `axis`.
+    pub fn product_axis(&self, axis: usize) -> Array<T, D>
+    where
+        T: Product,
+    {
+        self.map_axis(axis, |lane| lane.product())
+    }
+
+    /// The smallest element along `axis`, see [`min`](Self::min). The result has a length of 1
+    /// along `axis`, and contains `None` for lanes without comparable elements.
+    pub fn min_axis(&self, axis: usize) -> Array<Option<T>, D>
+    where
+        T: PartialOrd,
+    {
+        self.map_axis(axis, |lane| lane.min().cloned())
+    }
+
+    /// The largest element along `axis`, see [`max`](Self::max). The result has a length of 1
+    /// along `axis`, and contains `None` for lanes without comparable elements.
+    pub fn max_axis(&self, axis: usize) -> Array<Option<T>, D>
+    where
+        T: PartialOrd,
+    {
+        self.map_axis(axis, |lane| lane.max().cloned())
+    }
+}
+
+impl<T, const D: usize, A: Allocator> Array<T, D, A> {
+    /// Calls `f` with a view of every 1D lane along `axis` and collects the results into an
+    /// array whose `axis` has length 1, see [`ArrayView::map_axis`].
+    pub fn map_axis<'a, B, F: FnMut(ArrayView<'a, T, 1>) -> B>(
+        &'a self,
+        axis: usize,
+        f: F,
+    ) -> Array<B, D> {
+        self.view().map_axis(axis, f)
+    }
+
+    /// Folds every 1D lane along `axis` into a single value, starting with `init`. The result
+    /// has a length of 1 along `axis`.
+    pub fn fold_axis<'a, B: Clone, F: FnMut(B, &'a T) -> B>(
+        &'a self,
+        axis: usize,
+        init: B,
+        f: F,
+    ) -> Array<B, D> {
+        self.view().fold_axis(axis, init, f)
+    }
+
+    /// The smallest element, ignoring elements that are not comparable to themselves (NaN).
+    pub fn min(&self) -> Option<&T>
+    where
+        T: PartialOrd,
+    {
+        self.view().min()
+    }
+
+    /// The largest element, ignoring elements that are not comparable to themselves (NaN).
+    pub fn max(&self) -> Option<&T>
+    where
+        T: PartialOrd,
+    {
+        self.view().max()
+    }
+
+    /// The location of the first element for which `compare` returns
+    /// [`Less`](Ordering::Less) against every element before it.
+    pub fn argmin_by<F: FnMut(&T, &T) -> Ordering>(&self, compare: F) -> Option<[usize; D]> {
+        self.view().argmin_by(compare)
+    }
+
+    /// The location of the first element for which `compare` returns
+    /// [`Greater`](Ordering::Greater) against every element before it.
+    pub fn argmax_by<F: FnMut(&T, &T) -> Ordering>(&self, compare: F) -> Option<[usize; D]> {
+        self.view().argmax_by(compare)
+    }
+
+    /// The location of the first element with the smallest key.
+    pub fn argmin_by_key<K: Ord, F: FnMut(&T) -> K>(&self, f: F) -> Option<[usize; D]> {
+        self.view().argmin_by_key(f)
+    }
+
+    /// The location of the first element with the largest key.
+    pub fn argmax_by_key<K: Ord, F: FnMut(&T) -> K>(&self, f: F) -> Option<[usize; D]> {
+        self.view().argmax_by_key(f)
+    }
+
+    /// The location of the first smallest element, ignoring elements that are not comparable to
+    /// themselves (NaN).
+    pub fn argmin(&self) -> Option<[usize; D]>
+    where
+        T: PartialOrd,
+    {
+        self.view().argmin()
+    }
+
+    /// The location of the first largest element, ignoring elements that are not comparable to
+    /// themselves (NaN).
+    pub fn argmax(&self) -> Option<[usize; D]>
+    where
+        T: PartialOrd,
+    {
+        self.view().argmax()
+    }
+
+    /// The index along `axis` of the smallest element of every lane along `axis`. The result
+    /// has a length of 1 along `axis`, and contains `None` for lanes without comparable elements.
+    pub fn argmin_axis(&self, axis: usize) -> Array<Option<usize>, D>
+    where
+        T: PartialOrd,
+    {
+        self.view().argmin_axis(axis)
+    }
+
+    /// The index along `axis` of the largest element of every lane along `axis`. The result
+    /// has a length of 1 along `axis`, and contains `None` for lanes without comparable elements.
+    pub fn argmax_axis(&self, axis: usize) -> Array<Option<usize>, D>
+    where
+        T: PartialOrd,
+    {
+        self.view().argmax_axis(axis)
+    }
+}
+
+impl<T: Clone, const D: usize, A: Allocator> Array<T, D, A> {
+    pub fn sum(&self) -> T
+    where
+        T: Sum,
+    {
+        self.view().sum()
+    }
+
+    pub fn product(&self) -> T
+    where
+        T: Product,
+    {
+        self.view().product()
+    }
+
+    /// Sums up the elements along `axis`. The result has a length of 1 along `axis`.
+    pub fn sum_axis(&self, axis: usize) -> Array<T, D>
+    where
+        T: Sum,
+    {
+        self.view().sum_axis(axis)
+    }
+
+    /// Multiplies the elements along `axis`. The result has a length of 1 along `axis`.
+    pub fn product_axis(&self, axis: usize) -> Array<T, D>
+    where
+        T: Product,
+    {
+        self.view().product_axis(axis)
+    }
+
+    /// The smallest element along `axis`, see [`ArrayView::min_axis`].
+    pub fn min_axis(&self, axis: usize) -> Array<Option<T>, D>
+    where
+        T: PartialOrd,
+    {
+        self.view().min_axis(axis)
+    }
+
+    /// The largest element along `axis`, see [`ArrayView::max_axis`].
+    pub fn max_axis(&self, axis: usize) -> Array<Option<T>, D>
+    where
+        T: PartialOrd,
+    {
+        self.view().max_axis(axis)
+    }
+}
+
+macro_rules! impl_float_stats {
+    ($($t:ty),*) => {
+        $(
+            impl<const D: usize> ArrayView<'_, $t, D> {
+                /// The arithmetic mean of all elements, or `None` if the view is empty.
+                pub fn mean(&self) -> Option<$t> {
+                    let len = self.size.iter().product::<usize>();
+                    if len == 0 {
+                        return None;
+                    }
+                    Some(self.sum() / len as $t)
+                }
+
+                /// The population variance of all elements, or `None` if the view is empty.
+                pub fn variance(&self) -> Option<$t> {
+                    let mean = self.mean()?;
+                    let len = self.size.iter().product::<usize>();
+                    Some(self.iter().map(|(_, x)| (x - mean) * (x - mean)).sum::<$t>() / len as $t)
+                }
+
+                /// The population standard deviation of all elements, or `None` if the view is
+                /// empty.
+                pub fn std_dev(&self) -> Option<$t> {
+                    self.variance().map(|x| x.sqrt())
+                }
+
+                /// The mean of the elements along `axis`. The result has a length of 1 along
+                /// `axis`, and contains NaN if `axis` has a length of 0.
+                pub fn mean_axis(&self, axis: usize) -> Array<$t, D> {
+                    self.map_axis(axis, |lane| lane.mean().unwrap_or(<$t>::NAN))
+                }
+            }
+
+            impl<const D: usize, A: Allocator> Array<$t, D, A> {
+                /// The arithmetic mean of all elements, or `None` if the array is empty.
+                pub fn mean(&self) -> Option<$t> {
+                    self.view().mean()
+                }
+
+                /// The population variance of all elements, or `None` if the array is empty.
+                pub fn variance(&self) -> Option<$t> {
+                    self.view().variance()
+                }
+
+                /// The population standard deviation of all elements, or `None` if the array is
+                /// empty.
+                pub fn std_dev(&self) -> Option<$t> {
+                    self.view().std_dev()
+                }
+
+                /// The mean of the elements along `axis`. The result has a length of 1 along
+                /// `axis`, and contains NaN if `axis` has a length of 0.
+                pub fn mean_axis(&self, axis: usize) -> Array<$t, D> {
+                    self.view().mean_axis(axis)
+                }
+            }
+        )*
+    };
+}
+
+impl_float_stats!(f32, f64);
+
+#[cfg(test)]
+mod test {
+    use crate::Array;
+
+    #[test]
+    fn reductions() {
+        let array = Array::new_by_enumeration([3, 2], |i| i as i32 + 1);
+        assert_eq!(array.sum(), 21);
+        assert_eq!(array.product(), 720);
+        assert_eq!(array.min(), Some(&1));
+        assert_eq!(array.max(), Some(&6));
+        let sums = array.sum_axis(0);
+        assert_eq!(sums.size(), [1, 2]);
+        assert_eq!(sums.as_flattened(), &[6, 15]);
+        assert_eq!(array.product_axis(1).as_flattened(), &[4, 10, 18]);
+        assert_eq!(
+            array.slice(crate::s![1.., ..]).sum_axis(0).as_flattened(),
+            &[5, 11]
+        );
+
+        let floats =
+            Array::from_flat(vec![3.0, f64::NAN, 1.0, f64::NAN, 5.0, f64::NAN], [2, 3]).unwrap();
+        let mins = floats.min_axis(0);
+        assert_eq!(mins.size(), [1, 3]);
+        assert_eq!(mins.as_flattened(), &[Some(3.0), Some(1.0), Some(5.0)]);
+        assert_eq!(
+            array.max_axis(1).as_flattened(),
+            &[Some(4), Some(5), Some(6)]
+        );
+        assert_eq!(
+            floats.slice(crate::s![1.., ..]).max_axis(1).as_flattened(),
+            &[None]
+        );
+        assert_eq!(
+            Array::<i32, 2>::new([0, 2]).min_axis(0).as_flattened(),
+            &[None, None]
+        );
+    }
+
+    #[test]
+    fn statistics() {
+        let array =
+            Array::from_flat(vec![2.0f64, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0], [4, 2]).unwrap();
+        assert_eq!(array.mean(), Some(5.0));
+        assert_eq!(array.variance(), Some(4.0));
+        assert_eq!(array.std_dev(), Some(2.0));
+        assert_eq!(array.mean_axis(0).as_flattened(), &[3.5, 6.5]);
+        assert_eq!(Array::<f32, 2>::new([0, 2]).mean(), None);
+
+        let array = Array::from_flat(vec![f32::NAN, 1.0, 3.0], [3]).unwrap();
+        assert_eq!(array.min(), Some(&1.0));
+        assert_eq!(array.max(), Some(&3.0));
+        assert!(array.mean().unwrap().is_nan());
+    }
+
+    #[test]
+    fn custom_reductions() {
+        let array = Array::from_flat(vec![3, 0, 1, 0, 5, 2, 4, 0, 6], [3, 3]).unwrap();
+        let medians = array.map_axis(0, |lane| {
+            let mut lane = lane.iter().map(|(_, x)| *x).collect::<Vec<_>>();
+            lane.sort();
+            lane[lane.len() / 2]
+        });
+        assert_eq!(medians.as_flattened(), &[1, 2, 4]);
+        let first_nonzero = array.fold_axis(1, None, |acc, x| acc.or((*x != 0).then_some(*x)));
+        assert_eq!(first_nonzero.size(), [3, 1]);
+        assert_eq!(first_nonzero.as_flattened(), &[Some(3), Some(5), Some(1)]);
+    }
+
+    #[test]
+    fn arg_reductions() {
+        let heat = Array::from_flat(vec![1i32, 7, 3, 7, 0, 2], [3, 2]).unwrap();
+        assert_eq!(heat.argmax(), Some([1, 0]));
+        assert_eq!(heat.argmin(), Some([1, 1]));
+        assert_eq!(heat.argmax_by_key(|x| (*x - 3).abs()), Some([1, 0]));
+        assert_eq!(heat.argmin_by(|a, b| b.cmp(a)), Some([1, 0]));
+        assert_eq!(heat.argmax_axis(0).as_flattened(), &[Some(1), Some(0)]);
+        assert_eq!(
+            heat.argmin_axis(1).as_flattened(),
+            &[Some(0), Some(1), Some(1)]
+        );
+        assert_eq!(Array::<i32, 2>::new([0, 0]).argmax(), None);
+    }
+}
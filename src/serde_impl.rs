@@ -0,0 +1,237 @@
+use std::{fmt, marker::PhantomData};
+
+use serde::{
+    de::{self, DeserializeSeed, MapAccess, SeqAccess, Visitor},
+    ser::{SerializeStruct, SerializeTuple},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::{array::Allocator, Array};
+
+mod storage {
+    #[cfg(feature = "allocator")]
+    use crate::array::Allocator;
+    use crate::{Array, Error};
+
+    /// Creates arrays from deserialized data, which is stored differently with and without the
+    /// `allocator` feature.
+    pub trait FromData<T, const D: usize>: Sized {
+        type Data: Extend<T>;
+
+        fn new_data() -> Self::Data;
+
+        fn from_data(size: [usize; D], data: Self::Data) -> Result<Self, Error>;
+    }
+
+    #[cfg(not(feature = "allocator"))]
+    impl<T, const D: usize> FromData<T, D> for Array<T, D> {
+        type Data = Vec<T>;
+
+        fn new_data() -> Self::Data {
+            Vec::new()
+        }
+
+        fn from_data(size: [usize; D], data: Self::Data) -> Result<Self, Error> {
+            Array::try_from_flat(data, size)
+        }
+    }
+
+    #[cfg(feature = "allocator")]
+    impl<T, const D: usize, A: Allocator + Default> FromData<T, D> for Array<T, D, A> {
+        type Data = Vec<T, A>;
+
+        fn new_data() -> Self::Data {
+            Vec::new_in(A::default())
+        }
+
+        fn from_data(size: [usize; D], data: Self::Data) -> Result<Self, Error> {
+            Array::try_from_flat(data, size)
+        }
+    }
+}
+
+use storage::FromData;
+
+/// Serializes the size as a tuple, because serde only supports arrays of fixed lengths.
+struct Size<const D: usize>([usize; D]);
+
+impl<const D: usize> Serialize for Size<D> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut tuple = serializer.serialize_tuple(D)?;
+        for len in &self.0 {
+            tuple.serialize_element(len)?;
+        }
+        tuple.end()
+    }
+}
+
+impl<'de, const D: usize> Deserialize<'de> for Size<D> {
+    fn deserialize<De: Deserializer<'de>>(deserializer: De) -> Result<Self, De::Error> {
+        struct SizeVisitor<const D: usize>;
+
+        impl<'de, const D: usize> Visitor<'de> for SizeVisitor<D> {
+            type Value = Size<D>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "a tuple of {D} lengths")
+            }
+
+            fn visit_seq<S: SeqAccess<'de>>(self, mut seq: S) -> Result<Self::Value, S::Error> {
+                let mut size = [0; D];
+                for (i, len) in size.iter_mut().enumerate() {
+                    *len = seq
+                        .next_element()?
+                        .ok_or_else(|| de::Error::invalid_length(i, &self))?;
+                }
+                Ok(Size(size))
+            }
+        }
+
+        deserializer.deserialize_tuple(D, SizeVisitor)
+    }
+}
+
+/// Serializes the elements of an array in x-first order.
+struct Data<'a, T, const D: usize, A: Allocator>(&'a Array<T, D, A>);
+
+impl<T: Serialize, const D: usize, A: Allocator> Serialize for Data<'_, T, D, A> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.0.iter().map(|(_, x)| x))
+    }
+}
+
+/// Deserializes the elements of an array into the storage of `C`.
+struct DataSeed<T, C, const D: usize>(PhantomData<(T, C)>);
+
+impl<'de, T: Deserialize<'de>, C: FromData<T, D>, const D: usize> DeserializeSeed<'de>
+    for DataSeed<T, C, D>
+{
+    type Value = C::Data;
+
+    fn deserialize<De: Deserializer<'de>>(
+        self,
+        deserializer: De,
+    ) -> Result<Self::Value, De::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de, T: Deserialize<'de>, C: FromData<T, D>, const D: usize> Visitor<'de>
+    for DataSeed<T, C, D>
+{
+    type Value = C::Data;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a sequence of elements")
+    }
+
+    fn visit_seq<S: SeqAccess<'de>>(self, mut seq: S) -> Result<Self::Value, S::Error> {
+        let mut data = C::new_data();
+        while let Some(x) = seq.next_element()? {
+            data.extend([x]);
+        }
+        Ok(data)
+    }
+}
+
+/// Serializes the size as a tuple and the elements in x-first order, regardless of the memory
+/// layout. The allocator is not serialized.
+impl<T: Serialize, const D: usize, A: Allocator> Serialize for Array<T, D, A> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("Array", 2)?;
+        state.serialize_field("size", &Size(self.size))?;
+        state.serialize_field("data", &Data(self))?;
+        state.end()
+    }
+}
+
+const FIELDS: &[&str] = &["size", "data"];
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "lowercase")]
+enum Field {
+    Size,
+    Data,
+}
+
+struct ArrayVisitor<T, C, const D: usize>(PhantomData<(T, C)>);
+
+impl<'de, T: Deserialize<'de>, C: FromData<T, D>, const D: usize> Visitor<'de>
+    for ArrayVisitor<T, C, D>
+{
+    type Value = C;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a {D}D array")
+    }
+
+    fn visit_seq<S: SeqAccess<'de>>(self, mut seq: S) -> Result<Self::Value, S::Error> {
+        let Size(size) = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let data = seq
+            .next_element_seed(DataSeed::<T, C, D>(PhantomData))?
+            .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+        C::from_data(size, data).map_err(de::Error::custom)
+    }
+
+    fn visit_map<M: MapAccess<'de>>(self, mut map: M) -> Result<Self::Value, M::Error> {
+        let mut size = None;
+        let mut data = None;
+        while let Some(key) = map.next_key()? {
+            match key {
+                Field::Size => {
+                    if size.is_some() {
+                        return Err(de::Error::duplicate_field("size"));
+                    }
+                    size = Some(map.next_value::<Size<D>>()?.0);
+                }
+                Field::Data => {
+                    if data.is_some() {
+                        return Err(de::Error::duplicate_field("data"));
+                    }
+                    data = Some(map.next_value_seed(DataSeed::<T, C, D>(PhantomData))?);
+                }
+            }
+        }
+        let size = size.ok_or_else(|| de::Error::missing_field("size"))?;
+        let data = data.ok_or_else(|| de::Error::missing_field("data"))?;
+        C::from_data(size, data).map_err(de::Error::custom)
+    }
+}
+
+/// Deserializes what [`Serialize`] wrote, returning an error if the number of elements does
+/// not match the size. With the `allocator` feature, the allocator is created with
+/// [`Default`].
+impl<'de, T: Deserialize<'de>, const D: usize, A: Allocator> Deserialize<'de> for Array<T, D, A>
+where
+    Self: FromData<T, D>,
+{
+    fn deserialize<De: Deserializer<'de>>(deserializer: De) -> Result<Self, De::Error> {
+        deserializer.deserialize_struct("Array", FIELDS, ArrayVisitor::<T, Self, D>(PhantomData))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Array;
+
+    #[test]
+    fn round_trip() {
+        let array = Array::new_by_enumeration([3, 2], |i| i).transpose();
+        let json = serde_json::to_string(&array).unwrap();
+        assert_eq!(json, r#"{"size":[2,3],"data":[0,3,1,4,2,5]}"#);
+        let back: Array<usize, 2> = serde_json::from_str(&json).unwrap();
+        assert!(back.is_standard_layout());
+        assert_eq!(
+            back.iter().collect::<Vec<_>>(),
+            array.iter().collect::<Vec<_>>()
+        );
+
+        let error = serde_json::from_str::<Array<usize, 2>>(r#"{"size":[2,2],"data":[1,2,3]}"#)
+            .err()
+            .unwrap();
+        assert!(error.to_string().contains("Shape with 4 elements"));
+        assert!(serde_json::from_str::<Array<usize, 3>>(&json).is_err());
+    }
+}
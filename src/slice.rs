@@ -0,0 +1,174 @@
+use crate::Error;
+use std::ops::{Range, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive};
+
+/// A range with a step size along one dimension. Used by the `slice` functions of
+/// [`Array`](crate::Array) and its views, usually created through the [`s!`](crate::s) macro.
+///
+/// A negative step walks the selected range backwards, starting at its last element:
+/// `s![0..5;-2]` selects the elements 4, 2 and 0.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Slice {
+    pub start: usize,
+    /// The exclusive end of the range. `None` means the end of the dimension.
+    pub end: Option<usize>,
+    pub step: isize,
+}
+
+impl Slice {
+    pub fn new(start: usize, end: Option<usize>, step: isize) -> Self {
+        Self { start, end, step }
+    }
+
+    /// Multiplies the step size of this slice by `step`.
+    pub fn step_by(self, step: isize) -> Self {
+        Self {
+            step: self.step * step,
+            ..self
+        }
+    }
+}
+
+impl From<Range<usize>> for Slice {
+    fn from(value: Range<usize>) -> Self {
+        Self::new(value.start, Some(value.end), 1)
+    }
+}
+
+impl From<RangeInclusive<usize>> for Slice {
+    fn from(value: RangeInclusive<usize>) -> Self {
+        Self::new(*value.start(), Some(*value.end() + 1), 1)
+    }
+}
+
+impl From<RangeFrom<usize>> for Slice {
+    fn from(value: RangeFrom<usize>) -> Self {
+        Self::new(value.start, None, 1)
+    }
+}
+
+impl From<RangeTo<usize>> for Slice {
+    fn from(value: RangeTo<usize>) -> Self {
+        Self::new(0, Some(value.end), 1)
+    }
+}
+
+impl From<RangeToInclusive<usize>> for Slice {
+    fn from(value: RangeToInclusive<usize>) -> Self {
+        Self::new(0, Some(value.end + 1), 1)
+    }
+}
+
+impl From<RangeFull> for Slice {
+    fn from(_: RangeFull) -> Self {
+        Self::new(0, None, 1)
+    }
+}
+
+/// A single index, which selects a dimension of length 1.
+impl From<usize> for Slice {
+    fn from(value: usize) -> Self {
+        Self::new(value, Some(value + 1), 1)
+    }
+}
+
+/// Creates an array of [`Slice`]s for use with the `slice` functions. Every dimension is given
+/// as a range (or a single index), optionally followed by `;step`.
+///
+/// ```
+/// use micro_ndarray::{s, Array};
+///
+/// let array = Array::new_by_enumeration([4, 4], |i| i);
+/// // the interior of the array
+/// assert_eq!(array.slice(s![1..3, 1..3]).size(), [2, 2]);
+/// // every other column, reversed
+/// let view = array.slice(s![..;-2, ..]);
+/// assert_eq!(view[[0, 0]], 3);
+/// assert_eq!(view[[1, 0]], 1);
+/// ```
+#[macro_export]
+macro_rules! s {
+    ($($range:expr $(;$step:expr)?),* $(,)?) => {
+        [$($crate::Slice::from($range)$(.step_by($step))?),*]
+    };
+}
+
+/// Applies `info` to a size and stride, returning the offset of the first element along with
+/// the new size and stride.
+pub(crate) fn slice_parts<const D: usize>(
+    size: [usize; D],
+    stride: [isize; D],
+    info: [Slice; D],
+) -> Result<(isize, [usize; D], [isize; D]), Error> {
+    let mut offset = 0;
+    let mut new_size = size;
+    let mut new_stride = stride;
+    for (i, slice) in info.into_iter().enumerate() {
+        let end = slice.end.unwrap_or(size[i]);
+        if end > size[i] {
+            return Err(Error::OutOfBounds {
+                axis: i,
+                index: end,
+                len: size[i],
+            });
+        }
+        if slice.start > end {
+            return Err(Error::OutOfBounds {
+                axis: i,
+                index: slice.start,
+                len: end,
+            });
+        }
+        if slice.step == 0 {
+            return Err(Error::ZeroStep { axis: i });
+        }
+        let len = end - slice.start;
+        let step = slice.step.unsigned_abs();
+        new_size[i] = len.div_ceil(step);
+        new_stride[i] = stride[i] * slice.step;
+        if len == 0 {
+            continue;
+        }
+        let first = if slice.step > 0 { slice.start } else { end - 1 };
+        offset += first as isize * stride[i];
+    }
+    if new_size.contains(&0) {
+        // an empty view has no elements to point at, and the pointer may be dangling
+        offset = 0;
+    }
+    Ok((offset, new_size, new_stride))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Array;
+
+    #[test]
+    fn slicing() {
+        let mut array = Array::new_by_enumeration([5, 4], |i| i);
+        let interior = array.slice(s![1..4, 1..3]);
+        assert_eq!(interior.size(), [3, 2]);
+        assert_eq!(interior[[0, 0]], 6);
+        let sub = array.slice(s![..;2, 1]);
+        assert_eq!(sub.iter().map(|x| *x.1).collect::<Vec<_>>(), vec![5, 7, 9]);
+        let reversed = array.slice(s![0..5;-2, 3..;-1]);
+        assert_eq!(reversed.size(), [3, 1]);
+        assert_eq!(reversed[[0, 0]], 19);
+        assert_eq!(reversed[[2, 0]], 15);
+        assert_eq!(array.slice(s![2..2, ..]).iter().count(), 0);
+        let empty = Array::<u8, 2>::new([3, 0]);
+        assert_eq!(empty.slice(s![2.., ..]).size(), [1, 0]);
+
+        array
+            .slice_mut(s![.., 0..=0])
+            .iter_mut()
+            .for_each(|x| *x.1 = 0);
+        assert_eq!(&array.as_flattened()[..6], &[0, 0, 0, 0, 0, 5]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn out_of_bounds() {
+        let array = Array::new_with([5, 4], 0);
+        array.slice(s![..6, ..]);
+    }
+}
@@ -0,0 +1,221 @@
+//! Flipping, rolling and rotating arrays. On views, flips and rotations only rewrite the
+//! pointer, sizes and strides. Arrays can not have negative strides, so they move their
+//! elements in place instead.
+
+use crate::{
+    array::Allocator, concatenate, lanes::lane_parts, view::check_axis, Array, ArrayView,
+    ArrayViewMut,
+};
+
+/// Reverses `axis` of a view by pointing at its last element and negating the stride. Returns
+/// the offset of the new first element.
+fn flip_parts<const D: usize>(size: [usize; D], stride: &mut [isize; D], axis: usize) -> isize {
+    check_axis::<D>(axis);
+    if size.contains(&0) {
+        // an empty view has no elements to point at, and the pointer may be dangling
+        return 0;
+    }
+    let offset = (size[axis] - 1) as isize * stride[axis];
+    stride[axis] = -stride[axis];
+    offset
+}
+
+/// Checks the axes of a rotation and returns the number of quarter turns in `0..4`.
+fn rot90_turns<const D: usize>(a: usize, b: usize, k: i32) -> i32 {
+    check_axis::<D>(a);
+    check_axis::<D>(b);
+    assert_ne!(a, b, "Can not rotate within a single axis");
+    k.rem_euclid(4)
+}
+
+/// Performs a rotation with `swap_axes` and `flip`, which works the same for views and arrays.
+macro_rules! rot90 {
+    ($self:ident, $a:ident, $b:ident, $k:ident) => {
+        match rot90_turns::<D>($a, $b, $k) {
+            1 => {
+                $self.swap_axes($a, $b);
+                $self.flip($a);
+            }
+            2 => {
+                $self.flip($a);
+                $self.flip($b);
+            }
+            3 => {
+                $self.swap_axes($a, $b);
+                $self.flip($b);
+            }
+            _ => (),
+        }
+    };
+}
+
+impl<'a, T, const D: usize> ArrayView<'a, T, D> {
+    /// Reverses the order of the elements along `axis`. This is a zero-cost operation that
+    /// negates the stride of `axis`.
+    pub fn flip(&mut self, axis: usize) {
+        let offset = flip_parts(self.size, &mut self.stride, axis);
+        // SAFETY the offset points at the last element along axis
+        self.ptr = unsafe { self.ptr.offset(offset) };
+    }
+
+    /// Rotates the view by 90 degrees `k` times in the plane of axes `a` and `b`, in the
+    /// direction from `a` towards `b`. Negative `k` rotates the other way. This is a zero-cost
+    /// operation. Panics if `a` and `b` are the same axis.
+    pub fn rot90(&mut self, a: usize, b: usize, k: i32) {
+        rot90!(self, a, b, k);
+    }
+
+    /// Returns a copy of the view in standard layout whose elements are shifted by `shift`
+    /// along `axis`. Elements that are shifted past the end wrap around to the start, and
+    /// negative shifts move elements towards the start.
+    pub fn roll(&self, axis: usize, shift: isize) -> Array<T, D>
+    where
+        T: Clone,
+    {
+        check_axis::<D>(axis);
+        let len = self.size[axis];
+        let shift = if len == 0 {
+            0
+        } else {
+            shift.rem_euclid(len as isize) as usize
+        };
+        let (start, end) = self.split_at(axis, len - shift);
+        concatenate(axis, &[end, start]).unwrap()
+    }
+}
+
+impl<T, const D: usize> ArrayViewMut<'_, T, D> {
+    /// Reverses the order of the elements along `axis`, see [`ArrayView::flip`].
+    pub fn flip(&mut self, axis: usize) {
+        let offset = flip_parts(self.size, &mut self.stride, axis);
+        // SAFETY the offset points at the last element along axis
+        self.ptr = unsafe { self.ptr.offset(offset) };
+    }
+
+    /// Rotates the view by 90 degrees `k` times in the plane of axes `a` and `b`, see
+    /// [`ArrayView::rot90`].
+    pub fn rot90(&mut self, a: usize, b: usize, k: i32) {
+        rot90!(self, a, b, k);
+    }
+
+    /// Returns a copy of the view with its elements shifted along `axis`, see
+    /// [`ArrayView::roll`].
+    pub fn roll(&self, axis: usize, shift: isize) -> Array<T, D>
+    where
+        T: Clone,
+    {
+        self.view().roll(axis, shift)
+    }
+}
+
+impl<T, const D: usize, A: Allocator> Array<T, D, A> {
+    /// Reverses the order of the elements along `axis` by swapping them in place.
+    pub fn flip(&mut self, axis: usize) {
+        let (cursor, len, lane_stride) =
+            lane_parts(self.size, self.stride.map(|x| x as isize), axis);
+        let ptr = self.data.as_mut_ptr();
+        for (_, offset) in cursor {
+            for i in 0..len / 2 {
+                // SAFETY both elements are in the same lane of the array, and never the same
+                unsafe {
+                    std::ptr::swap(
+                        ptr.offset(offset + i as isize * lane_stride),
+                        ptr.offset(offset + (len - 1 - i) as isize * lane_stride),
+                    )
+                }
+            }
+        }
+    }
+
+    /// Rotates the array by 90 degrees `k` times in the plane of axes `a` and `b`, see
+    /// [`ArrayView::rot90`]. This swaps the two axes if `k` is odd, and flips the elements in
+    /// place.
+    pub fn rot90(&mut self, a: usize, b: usize, k: i32) {
+        rot90!(self, a, b, k);
+    }
+
+    /// Returns a copy of the array with its elements shifted along `axis`, see
+    /// [`ArrayView::roll`].
+    pub fn roll(&self, axis: usize, shift: isize) -> Array<T, D>
+    where
+        T: Clone,
+    {
+        self.view().roll(axis, shift)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Array;
+
+    #[test]
+    fn flip() {
+        let mut array = Array::new_by_enumeration([3, 2], |i| i);
+        let mut view = array.view();
+        view.flip(0);
+        assert_eq!(view[[0, 1]], 5);
+        view.flip(1);
+        assert_eq!(
+            view.iter().map(|(_, x)| *x).collect::<Vec<_>>(),
+            [5, 4, 3, 2, 1, 0]
+        );
+
+        array.flip(0);
+        assert_eq!(array.as_flattened(), &[2, 1, 0, 5, 4, 3]);
+        array.swap_axes(0, 1);
+        array.flip(0);
+        assert_eq!(array.as_flattened(), &[5, 4, 3, 2, 1, 0]);
+        let mut view = array.view_mut();
+        view.flip(1);
+        assert_eq!(view[[0, 0]], 3);
+
+        let empty = Array::<u32, 2>::new([3, 0]);
+        let mut view = empty.view();
+        view.flip(0);
+        assert_eq!(view.size(), [3, 0]);
+        assert_eq!(view.iter().count(), 0);
+    }
+
+    #[test]
+    fn roll() {
+        let array = Array::new_by_enumeration([4, 2], |i| i);
+        assert_eq!(array.roll(0, 1).as_flattened(), &[3, 0, 1, 2, 7, 4, 5, 6]);
+        assert_eq!(array.roll(0, -5).as_flattened(), &[1, 2, 3, 0, 5, 6, 7, 4]);
+        assert_eq!(array.roll(1, 3).as_flattened(), &[4, 5, 6, 7, 0, 1, 2, 3]);
+        assert_eq!(array.roll(0, 8).as_flattened(), array.as_flattened());
+    }
+
+    #[test]
+    fn rot90() {
+        // 0 1 2
+        // 3 4 5
+        let mut array = Array::new_by_enumeration([3, 2], |i| i);
+        let mut view = array.view();
+        view.rot90(0, 1, 1);
+        assert_eq!(view.size(), [2, 3]);
+        // 3 0
+        // 4 1
+        // 5 2
+        assert_eq!(
+            view.iter().map(|(_, x)| *x).collect::<Vec<_>>(),
+            [3, 0, 4, 1, 5, 2]
+        );
+        view.rot90(0, 1, -1);
+        assert_eq!(
+            view.iter().map(|(_, x)| *x).collect::<Vec<_>>(),
+            [0, 1, 2, 3, 4, 5]
+        );
+
+        array.rot90(1, 0, 2);
+        assert_eq!(
+            array.iter().map(|(_, x)| *x).collect::<Vec<_>>(),
+            [5, 4, 3, 2, 1, 0]
+        );
+        array.rot90(0, 1, 3);
+        assert_eq!(array.size(), [2, 3]);
+        assert_eq!(
+            array.iter().map(|(_, x)| *x).collect::<Vec<_>>(),
+            [3, 0, 4, 1, 5, 2]
+        );
+    }
+}
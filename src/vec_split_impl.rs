@@ -6,11 +6,11 @@ use vec_split::{
 use crate::Array;
 
 impl<T, const D: usize, V: Vector<T, D>> VectorArray<T, D, V, [usize; D]> for Array<V, D> {
-    fn get<'a>(&'a self, index: [usize; D]) -> Option<&'a V> {
+    fn get(&self, index: [usize; D]) -> Option<&V> {
         Array::get(self, index)
     }
 
-    fn get_mut<'a>(&'a mut self, index: [usize; D]) -> Option<&'a mut V> {
+    fn get_mut(&mut self, index: [usize; D]) -> Option<&mut V> {
         Array::get_mut(self, index)
     }
 }
@@ -30,27 +30,17 @@ impl<T, const D: usize, V: RawVector<T, D>> SizedVectorArray<T, D, V, [usize; D]
 
     #[inline]
     fn convert_index(&self, index: [usize; D]) -> usize {
-        let mut real_loc = 0;
-        for (i, dim) in index.iter().enumerate() {
-            let mut dim = *dim;
-            if i == 0 {
-                real_loc += dim;
-                continue;
-            }
-            dim *= self.stride[i];
-            real_loc += dim;
-        }
-        real_loc
+        self.flat_index_unchecked(index)
     }
 }
 
-impl<'a, T, const D: usize> Accessor<T, [usize; D]> for Array<T, D> {
-    fn get<'b>(&'b self, index: [usize; D]) -> Option<&'b T> {
+impl<T, const D: usize> Accessor<T, [usize; D]> for Array<T, D> {
+    fn get(&self, index: [usize; D]) -> Option<&T> {
         <Array<T, D>>::get(self, index)
     }
 }
-impl<'a, T, const D: usize> AccessorMut<T, [usize; D]> for Array<T, D> {
-    fn get_mut<'b>(&'b mut self, index: [usize; D]) -> Option<&'b mut T> {
+impl<T, const D: usize> AccessorMut<T, [usize; D]> for Array<T, D> {
+    fn get_mut(&mut self, index: [usize; D]) -> Option<&mut T> {
         <Array<T, D>>::get_mut(self, index)
     }
 }
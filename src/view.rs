@@ -0,0 +1,443 @@
+use std::{
+    marker::PhantomData,
+    ops::{Index, IndexMut},
+};
+
+use crate::{
+    array::standard_stride,
+    error::Error,
+    iterator::{Iter, IterMut},
+    slice::{slice_parts, Slice},
+};
+
+/// A borrowed, read-only N-dimensional view into the elements of an [`Array`](crate::Array)
+/// or any other memory. Unlike an `Array`, a view carries its own `size` and `stride`, so it
+/// can describe any regularly spaced subset of the memory it borrows.
+pub struct ArrayView<'a, T, const D: usize> {
+    pub(crate) ptr: *const T,
+    pub(crate) size: [usize; D],
+    pub(crate) stride: [isize; D],
+    phantom: PhantomData<&'a T>,
+}
+
+/// A borrowed, mutable N-dimensional view. See [`ArrayView`].
+pub struct ArrayViewMut<'a, T, const D: usize> {
+    pub(crate) ptr: *mut T,
+    pub(crate) size: [usize; D],
+    pub(crate) stride: [isize; D],
+    phantom: PhantomData<&'a mut T>,
+}
+
+unsafe impl<T: Sync, const D: usize> Send for ArrayView<'_, T, D> {}
+unsafe impl<T: Sync, const D: usize> Sync for ArrayView<'_, T, D> {}
+unsafe impl<T: Send, const D: usize> Send for ArrayViewMut<'_, T, D> {}
+unsafe impl<T: Sync, const D: usize> Sync for ArrayViewMut<'_, T, D> {}
+
+impl<T, const D: usize> Clone for ArrayView<'_, T, D> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<T, const D: usize> Copy for ArrayView<'_, T, D> {}
+
+#[inline]
+fn internal_offset<const D: usize>(
+    size: &[usize; D],
+    stride: &[isize; D],
+    loc: [usize; D],
+) -> Result<isize, Error> {
+    let mut real_loc = 0;
+    for (i, &dim) in loc.iter().enumerate() {
+        if dim >= size[i] {
+            return Err(Error::OutOfBounds {
+                axis: i,
+                index: dim,
+                len: size[i],
+            });
+        }
+        real_loc += dim as isize * stride[i];
+    }
+    Ok(real_loc)
+}
+
+/// Panics if `axis` is not an axis of a `D` dimensional array.
+pub(crate) fn check_axis<const D: usize>(axis: usize) {
+    if axis >= D {
+        panic!(
+            "{}",
+            Error::InvalidAxis {
+                axis,
+                dimensions: D
+            }
+        )
+    }
+}
+
+/// Reorders size and stride so that axis `i` of the result is axis `axes[i]` of the input.
+pub(crate) fn permute_axes<S: Copy, const D: usize>(
+    size: [usize; D],
+    stride: [S; D],
+    axes: [usize; D],
+) -> ([usize; D], [S; D]) {
+    let mut used = [false; D];
+    for &axis in axes.iter() {
+        if axis >= D || used[axis] {
+            panic!("{axes:?} is not a permutation of the axes 0..{D}")
+        }
+        used[axis] = true;
+    }
+    (axes.map(|axis| size[axis]), axes.map(|axis| stride[axis]))
+}
+
+#[inline]
+fn unchecked_offset<const D: usize>(stride: &[isize; D], loc: [usize; D]) -> isize {
+    let mut real_loc = 0;
+    for (i, &dim) in loc.iter().enumerate() {
+        real_loc += dim as isize * stride[i];
+    }
+    real_loc
+}
+
+impl<'a, T, const D: usize> ArrayView<'a, T, D> {
+    /// Creates a view from a base pointer, a size and a stride for each dimension. The element
+    /// at `loc` is found at `ptr.offset(loc[0] * stride[0] + loc[1] * stride[1] + ...)`.
+    ///
+    /// # Safety
+    /// Every location within `size` must map to an element that is valid for reads for `'a`,
+    /// and no element may be mutated during `'a`.
+    pub unsafe fn from_raw_parts(ptr: *const T, size: [usize; D], stride: [isize; D]) -> Self {
+        Self {
+            ptr,
+            size,
+            stride,
+            phantom: PhantomData,
+        }
+    }
+
+    pub fn size(&self) -> [usize; D] {
+        self.size
+    }
+
+    /// The distance in elements between two neighbours along each dimension.
+    pub fn stride(&self) -> [isize; D] {
+        self.stride
+    }
+
+    pub fn get(&self, loc: [usize; D]) -> Option<&'a T> {
+        self.try_get(loc).ok()
+    }
+
+    /// Like [`get`](Self::get), but returns which axis was out of bounds on failure.
+    pub fn try_get(&self, loc: [usize; D]) -> Result<&'a T, Error> {
+        internal_offset(&self.size, &self.stride, loc)
+            // SAFETY this is checked by internal_offset
+            .map(|offset| unsafe { &*self.ptr.offset(offset) })
+    }
+
+    /// # Safety
+    /// `loc` must be within the size of the view.
+    pub unsafe fn get_unchecked(&self, loc: [usize; D]) -> &'a T {
+        &*self.ptr.offset(unchecked_offset(&self.stride, loc))
+    }
+
+    pub fn iter(&self) -> Iter<'a, T, D> {
+        // SAFETY views only describe valid elements
+        unsafe { Iter::new(self.ptr, self.size, self.stride) }
+    }
+
+    /// Swaps two axes of the view.
+    pub fn swap_axes(&mut self, a: usize, b: usize) {
+        self.size.swap(a, b);
+        self.stride.swap(a, b);
+    }
+
+    /// Reorders the axes of the view so that axis `i` of the result is axis `axes[i]` of
+    /// `self`. Panics if `axes` is not a permutation of `0..D`.
+    pub fn permuted_axes(mut self, axes: [usize; D]) -> Self {
+        (self.size, self.stride) = permute_axes(self.size, self.stride, axes);
+        self
+    }
+
+    /// Stretches the view to `size` without copying by giving every axis of length 1 a stride
+    /// of 0. All other axes must already have the requested size, and the number of elements
+    /// of `size` must fit into a usize.
+    pub fn broadcast_to(&self, size: [usize; D]) -> Result<ArrayView<'a, T, D>, Error> {
+        let mut stride = self.stride;
+        for axis in 0..D {
+            if self.size[axis] == size[axis] {
+                continue;
+            }
+            if self.size[axis] != 1 {
+                return Err(Error::IncompatibleShapes {
+                    axis,
+                    left: self.size[axis],
+                    right: size[axis],
+                });
+            }
+            stride[axis] = 0;
+        }
+        standard_stride(size)?;
+        // SAFETY the new view only repeats elements of this view
+        unsafe { Ok(ArrayView::from_raw_parts(self.ptr, size, stride)) }
+    }
+
+    /// Returns a view of the part of this view selected by `info`, see [`s!`](crate::s).
+    pub fn slice(&self, info: [Slice; D]) -> ArrayView<'a, T, D> {
+        self.try_slice(info).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Like [`slice`](Self::slice), but returns an error instead of panicking.
+    pub fn try_slice(&self, info: [Slice; D]) -> Result<ArrayView<'a, T, D>, Error> {
+        let (offset, size, stride) = slice_parts(self.size, self.stride, info)?;
+        // SAFETY slice_parts only selects elements of this view
+        unsafe {
+            Ok(ArrayView::from_raw_parts(
+                self.ptr.offset(offset),
+                size,
+                stride,
+            ))
+        }
+    }
+}
+
+impl<'a, T, const D: usize> ArrayViewMut<'a, T, D> {
+    /// Creates a mutable view from a base pointer, a size and a stride for each dimension. The
+    /// element at `loc` is found at `ptr.offset(loc[0] * stride[0] + loc[1] * stride[1] + ...)`.
+    ///
+    /// # Safety
+    /// Every location within `size` must map to an element that is valid for reads and writes
+    /// for `'a`, no two locations may map to the same element, and no element may be accessed
+    /// through anything but this view during `'a`.
+    pub unsafe fn from_raw_parts(ptr: *mut T, size: [usize; D], stride: [isize; D]) -> Self {
+        Self {
+            ptr,
+            size,
+            stride,
+            phantom: PhantomData,
+        }
+    }
+
+    pub fn size(&self) -> [usize; D] {
+        self.size
+    }
+
+    /// The distance in elements between two neighbours along each dimension.
+    pub fn stride(&self) -> [isize; D] {
+        self.stride
+    }
+
+    /// Borrows this view as a read-only view.
+    pub fn view(&self) -> ArrayView<'_, T, D> {
+        // SAFETY the returned view borrows self
+        unsafe { ArrayView::from_raw_parts(self.ptr, self.size, self.stride) }
+    }
+
+    /// Reborrows this view for a shorter lifetime.
+    pub fn view_mut(&mut self) -> ArrayViewMut<'_, T, D> {
+        // SAFETY the returned view mutably borrows self
+        unsafe { ArrayViewMut::from_raw_parts(self.ptr, self.size, self.stride) }
+    }
+
+    /// Turns this view into a read-only view with the same lifetime.
+    pub fn into_view(self) -> ArrayView<'a, T, D> {
+        // SAFETY self is consumed, so nothing can mutate the elements anymore
+        unsafe { ArrayView::from_raw_parts(self.ptr, self.size, self.stride) }
+    }
+
+    pub fn get(&self, loc: [usize; D]) -> Option<&T> {
+        self.view().get(loc)
+    }
+
+    /// Like [`get`](Self::get), but returns which axis was out of bounds on failure.
+    pub fn try_get(&self, loc: [usize; D]) -> Result<&T, Error> {
+        self.view().try_get(loc)
+    }
+
+    /// # Safety
+    /// `loc` must be within the size of the view.
+    pub unsafe fn get_unchecked(&self, loc: [usize; D]) -> &T {
+        &*self.ptr.offset(unchecked_offset(&self.stride, loc))
+    }
+
+    pub fn get_mut(&mut self, loc: [usize; D]) -> Option<&mut T> {
+        self.try_get_mut(loc).ok()
+    }
+
+    /// Like [`get_mut`](Self::get_mut), but returns which axis was out of bounds on failure.
+    pub fn try_get_mut(&mut self, loc: [usize; D]) -> Result<&mut T, Error> {
+        internal_offset(&self.size, &self.stride, loc)
+            // SAFETY this is checked by internal_offset
+            .map(|offset| unsafe { &mut *self.ptr.offset(offset) })
+    }
+
+    /// # Safety
+    /// `loc` must be within the size of the view.
+    pub unsafe fn get_unchecked_mut(&mut self, loc: [usize; D]) -> &mut T {
+        &mut *self.ptr.offset(unchecked_offset(&self.stride, loc))
+    }
+
+    pub fn iter(&self) -> Iter<'_, T, D> {
+        self.view().iter()
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T, D> {
+        self.view_mut().into_iter()
+    }
+
+    /// Swaps two axes of the view.
+    pub fn swap_axes(&mut self, a: usize, b: usize) {
+        self.size.swap(a, b);
+        self.stride.swap(a, b);
+    }
+
+    /// Reorders the axes of the view so that axis `i` of the result is axis `axes[i]` of
+    /// `self`. Panics if `axes` is not a permutation of `0..D`.
+    pub fn permuted_axes(mut self, axes: [usize; D]) -> Self {
+        (self.size, self.stride) = permute_axes(self.size, self.stride, axes);
+        self
+    }
+
+    /// Returns a view of the part of this view selected by `info`, see [`s!`](crate::s).
+    pub fn slice(&self, info: [Slice; D]) -> ArrayView<'_, T, D> {
+        self.view().slice(info)
+    }
+
+    /// Like [`slice`](Self::slice), but returns an error instead of panicking.
+    pub fn try_slice(&self, info: [Slice; D]) -> Result<ArrayView<'_, T, D>, Error> {
+        self.view().try_slice(info)
+    }
+
+    /// Returns a mutable view of the part of this view selected by `info`, see
+    /// [`s!`](crate::s).
+    pub fn slice_mut(&mut self, info: [Slice; D]) -> ArrayViewMut<'_, T, D> {
+        self.view_mut().into_slice(info)
+    }
+
+    /// Like [`slice_mut`](Self::slice_mut), but returns an error instead of panicking.
+    pub fn try_slice_mut(&mut self, info: [Slice; D]) -> Result<ArrayViewMut<'_, T, D>, Error> {
+        self.view_mut().try_into_slice(info)
+    }
+
+    /// Like [`slice_mut`](Self::slice_mut), but keeps the lifetime of this view.
+    pub fn into_slice(self, info: [Slice; D]) -> ArrayViewMut<'a, T, D> {
+        self.try_into_slice(info).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Like [`into_slice`](Self::into_slice), but returns an error instead of panicking.
+    pub fn try_into_slice(self, info: [Slice; D]) -> Result<ArrayViewMut<'a, T, D>, Error> {
+        let (offset, size, stride) = slice_parts(self.size, self.stride, info)?;
+        // SAFETY slice_parts only selects distinct elements of this view
+        unsafe {
+            Ok(ArrayViewMut::from_raw_parts(
+                self.ptr.offset(offset),
+                size,
+                stride,
+            ))
+        }
+    }
+}
+
+impl<T> ArrayView<'_, T, 2> {
+    /// Swaps the two axes of a 2D view.
+    pub fn transpose(self) -> Self {
+        self.permuted_axes([1, 0])
+    }
+}
+
+impl<T> ArrayViewMut<'_, T, 2> {
+    /// Swaps the two axes of a 2D view.
+    pub fn transpose(self) -> Self {
+        self.permuted_axes([1, 0])
+    }
+}
+
+impl<T, const D: usize> Index<[usize; D]> for ArrayView<'_, T, D> {
+    type Output = T;
+
+    fn index(&self, index: [usize; D]) -> &Self::Output {
+        let offset =
+            internal_offset(&self.size, &self.stride, index).unwrap_or_else(|e| panic!("{e}"));
+        // SAFETY this is checked by internal_offset
+        unsafe { &*self.ptr.offset(offset) }
+    }
+}
+
+impl<T, const D: usize> Index<[usize; D]> for ArrayViewMut<'_, T, D> {
+    type Output = T;
+
+    fn index(&self, index: [usize; D]) -> &Self::Output {
+        let offset =
+            internal_offset(&self.size, &self.stride, index).unwrap_or_else(|e| panic!("{e}"));
+        // SAFETY this is checked by internal_offset
+        unsafe { &*self.ptr.offset(offset) }
+    }
+}
+
+impl<T, const D: usize> IndexMut<[usize; D]> for ArrayViewMut<'_, T, D> {
+    fn index_mut(&mut self, index: [usize; D]) -> &mut Self::Output {
+        let offset =
+            internal_offset(&self.size, &self.stride, index).unwrap_or_else(|e| panic!("{e}"));
+        // SAFETY this is checked by internal_offset
+        unsafe { &mut *self.ptr.offset(offset) }
+    }
+}
+
+impl<'a, T, const D: usize> IntoIterator for ArrayView<'a, T, D> {
+    type Item = ([usize; D], &'a T);
+    type IntoIter = Iter<'a, T, D>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T, const D: usize> IntoIterator for ArrayViewMut<'a, T, D> {
+    type Item = ([usize; D], &'a mut T);
+    type IntoIter = IterMut<'a, T, D>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        // SAFETY views only describe valid, distinct elements
+        unsafe { IterMut::new(self.ptr, self.size, self.stride) }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Array, ArrayView, Error};
+
+    #[test]
+    fn strided_view() {
+        let array = Array::new_by_enumeration([4, 3], |i| i);
+        // every other column, walked backwards
+        let view = unsafe {
+            ArrayView::from_raw_parts(array.as_flattened().as_ptr().add(3), [2, 3], [-2, 4])
+        };
+        assert_eq!(view[[0, 0]], 3);
+        assert_eq!(view[[1, 2]], 9);
+        assert_eq!(view.get([2, 0]), None);
+        assert_eq!(
+            view.iter().map(|x| *x.1).collect::<Vec<_>>(),
+            vec![3, 1, 7, 5, 11, 9]
+        );
+    }
+
+    #[test]
+    fn view_mut() {
+        let mut array = Array::new_with([3, 2], 0);
+        let mut view = array.view_mut();
+        view[[2, 1]] = 5;
+        view.iter_mut().for_each(|(loc, x)| *x += loc[0]);
+        assert_eq!(array.as_flattened(), &[0, 1, 2, 0, 1, 7]);
+        assert_eq!(array.view().iter().count(), 6);
+    }
+
+    #[test]
+    fn broadcast_overflow() {
+        let array = Array::new_with([1, 2], 0);
+        assert_eq!(array.broadcast_to([4, 2]).unwrap().iter().count(), 8);
+        assert_eq!(
+            array.broadcast_to([usize::MAX, 2]).err(),
+            Some(Error::SizeOverflow)
+        );
+    }
+}
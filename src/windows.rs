@@ -0,0 +1,187 @@
+use std::marker::PhantomData;
+
+use crate::{array::Allocator, iterator::Cursor, Array, ArrayView, Error};
+
+/// Iterator over all overlapping sub-views of a fixed size, see [`ArrayView::windows`].
+pub struct Windows<'a, T, const D: usize> {
+    cursor: Cursor<D>,
+    ptr: *const T,
+    size: [usize; D],
+    stride: [isize; D],
+    phantom: PhantomData<&'a T>,
+}
+
+/// Which cells count as neighbours of a cell, see [`ArrayView::neighbours`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Neighbourhood {
+    /// All cells whose distance along every axis is at most the radius, which includes diagonals.
+    Moore,
+    /// All cells whose distances along all axes add up to at most the radius, which excludes
+    /// diagonals.
+    VonNeumann,
+}
+
+/// Iterator over the cells around a location, see [`ArrayView::neighbours`].
+pub struct Neighbours<'a, T, const D: usize> {
+    cursor: Cursor<D>,
+    ptr: *const T,
+    start: [usize; D],
+    center: [usize; D],
+    radius: usize,
+    kind: Neighbourhood,
+    phantom: PhantomData<&'a T>,
+}
+
+unsafe impl<T: Sync, const D: usize> Send for Windows<'_, T, D> {}
+unsafe impl<T: Sync, const D: usize> Sync for Windows<'_, T, D> {}
+unsafe impl<T: Sync, const D: usize> Send for Neighbours<'_, T, D> {}
+unsafe impl<T: Sync, const D: usize> Sync for Neighbours<'_, T, D> {}
+
+impl<'a, T, const D: usize> Iterator for Windows<'a, T, D> {
+    type Item = ArrayView<'a, T, D>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.cursor.next().map(|(_, offset)| {
+            // SAFETY the window starts at a location where it fits into the view
+            unsafe { ArrayView::from_raw_parts(self.ptr.offset(offset), self.size, self.stride) }
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.cursor.size_hint()
+    }
+}
+
+impl<T, const D: usize> ExactSizeIterator for Windows<'_, T, D> {}
+
+impl<'a, T, const D: usize> Iterator for Neighbours<'a, T, D> {
+    type Item = ([usize; D], &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for (rel, offset) in self.cursor.by_ref() {
+            let mut loc = rel;
+            let mut distance = 0;
+            for ((dim, start), center) in loc.iter_mut().zip(self.start).zip(self.center) {
+                *dim += start;
+                distance += dim.abs_diff(center);
+            }
+            if distance == 0 || (self.kind == Neighbourhood::VonNeumann && distance > self.radius) {
+                continue;
+            }
+            // SAFETY the cursor only walks the part of the view around the center
+            return Some((loc, unsafe { &*self.ptr.offset(offset) }));
+        }
+        None
+    }
+}
+
+impl<'a, T, const D: usize> ArrayView<'a, T, D> {
+    /// Iterates over all overlapping sub-views of `size` in x-first order of their first
+    /// element. Yields nothing if the window is larger than the view. Panics if any length of
+    /// `size` is 0.
+    pub fn windows(&self, size: [usize; D]) -> Windows<'a, T, D> {
+        let mut positions = [0; D];
+        for i in 0..D {
+            assert_ne!(size[i], 0, "Window size of axis {i} is 0");
+            positions[i] = (self.size[i] + 1).saturating_sub(size[i]);
+        }
+        Windows {
+            cursor: Cursor::new(positions, self.stride),
+            ptr: self.ptr,
+            size,
+            stride: self.stride,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Iterates over the cells around `loc` within `radius`, skipping `loc` itself and all
+    /// locations outside of the view. Panics if `loc` is out of bounds.
+    pub fn neighbours(
+        &self,
+        loc: [usize; D],
+        radius: usize,
+        kind: Neighbourhood,
+    ) -> Neighbours<'a, T, D> {
+        let mut start = [0; D];
+        let mut size = [0; D];
+        let mut offset = 0;
+        for i in 0..D {
+            if loc[i] >= self.size[i] {
+                panic!(
+                    "{}",
+                    Error::OutOfBounds {
+                        axis: i,
+                        index: loc[i],
+                        len: self.size[i]
+                    }
+                )
+            }
+            start[i] = loc[i].saturating_sub(radius);
+            size[i] = loc[i].saturating_add(radius).min(self.size[i] - 1) + 1 - start[i];
+            offset += start[i] as isize * self.stride[i];
+        }
+        Neighbours {
+            cursor: Cursor::new(size, self.stride),
+            // SAFETY start is within the view
+            ptr: unsafe { self.ptr.offset(offset) },
+            start,
+            center: loc,
+            radius,
+            kind,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<T, const D: usize, A: Allocator> Array<T, D, A> {
+    /// Iterates over all overlapping sub-views of `size`, see [`ArrayView::windows`].
+    pub fn windows(&self, size: [usize; D]) -> Windows<'_, T, D> {
+        self.view().windows(size)
+    }
+
+    /// Iterates over the cells around `loc` within `radius`, see [`ArrayView::neighbours`].
+    pub fn neighbours(
+        &self,
+        loc: [usize; D],
+        radius: usize,
+        kind: Neighbourhood,
+    ) -> Neighbours<'_, T, D> {
+        self.view().neighbours(loc, radius, kind)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Array, Neighbourhood};
+
+    #[test]
+    fn windows() {
+        let array = Array::new_by_enumeration([4, 3], |i| i);
+        let sums = array
+            .windows([3, 2])
+            .map(|window| window.sum())
+            .collect::<Vec<_>>();
+        assert_eq!(sums, vec![18, 24, 42, 48]);
+        assert_eq!(array.windows([5, 1]).count(), 0);
+        assert_eq!(array.windows([1, 1]).len(), 12);
+    }
+
+    #[test]
+    fn neighbours() {
+        let array = Array::new_by_enumeration([4, 3], |i| i);
+        let moore = array
+            .neighbours([0, 1], 1, Neighbourhood::Moore)
+            .map(|(_, x)| *x)
+            .collect::<Vec<_>>();
+        assert_eq!(moore, vec![0, 1, 5, 8, 9]);
+        let von_neumann = array
+            .neighbours([1, 1], 1, Neighbourhood::VonNeumann)
+            .map(|(loc, _)| loc)
+            .collect::<Vec<_>>();
+        assert_eq!(von_neumann, vec![[1, 0], [0, 1], [2, 1], [1, 2]]);
+        assert_eq!(
+            array.neighbours([3, 2], 5, Neighbourhood::Moore).count(),
+            11
+        );
+    }
+}
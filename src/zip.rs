@@ -0,0 +1,331 @@
+//! Element-wise mapping and lockstep traversal of several arrays. [`Zip`] walks up to six
+//! arrays or views of the same size at once, yielding the location of every element together
+//! with a reference into each of them.
+
+use crate::{
+    array::{standard_stride, Allocator},
+    Array, ArrayView, ArrayViewMut, Error,
+};
+
+/// Something [`Zip`] can walk over: an array or view that yields its elements in x-first order
+/// together with their locations.
+pub trait Producer<const D: usize>: IntoIterator<Item = ([usize; D], Self::Elem)> {
+    /// The reference type that is yielded for every element.
+    type Elem;
+
+    /// The size of the producer along every axis.
+    fn size(&self) -> [usize; D];
+}
+
+impl<'a, T, const D: usize> Producer<D> for ArrayView<'a, T, D> {
+    type Elem = &'a T;
+
+    fn size(&self) -> [usize; D] {
+        self.size
+    }
+}
+
+impl<'a, T, const D: usize> Producer<D> for ArrayViewMut<'a, T, D> {
+    type Elem = &'a mut T;
+
+    fn size(&self) -> [usize; D] {
+        self.size
+    }
+}
+
+impl<'a, T, const D: usize, A: Allocator> Producer<D> for &'a Array<T, D, A> {
+    type Elem = &'a T;
+
+    fn size(&self) -> [usize; D] {
+        self.size
+    }
+}
+
+impl<'a, T, const D: usize, A: Allocator> Producer<D> for &'a mut Array<T, D, A> {
+    type Elem = &'a mut T;
+
+    fn size(&self) -> [usize; D] {
+        self.size
+    }
+}
+
+/// Builder for walking several producers of the same size in lockstep. Start with
+/// [`Zip::new`] and add up to five more producers with [`and`](Zip::and), then iterate over
+/// it or call `for_each`.
+///
+/// ```
+/// use micro_ndarray::{Array, Zip};
+///
+/// let velocity = Array::new_with([3, 2], 2.0);
+/// let mask = Array::new_by_enumeration([3, 2], |i| i % 3 != 1);
+/// let mut position = Array::new_with([3, 2], 1.0);
+/// Zip::new(&mut position)
+///     .and(&velocity)
+///     .and(&mask)
+///     .for_each(|_, p, v, m| {
+///         if *m {
+///             *p += v
+///         }
+///     });
+/// assert_eq!(position.as_flattened(), &[3.0, 1.0, 3.0, 3.0, 1.0, 3.0]);
+/// ```
+pub struct Zip<P, const D: usize> {
+    parts: P,
+    size: [usize; D],
+}
+
+/// Iterator over the elements of a [`Zip`].
+pub struct ZipIter<I, const D: usize> {
+    iters: I,
+}
+
+/// Checks that `right` can be walked in lockstep with something of `left`.
+fn check_size<const D: usize>(left: [usize; D], right: [usize; D]) -> Result<(), Error> {
+    for axis in 0..D {
+        if left[axis] != right[axis] {
+            return Err(Error::IncompatibleShapes {
+                axis,
+                left: left[axis],
+                right: right[axis],
+            });
+        }
+    }
+    Ok(())
+}
+
+impl<P: Producer<D>, const D: usize> Zip<(P,), D> {
+    /// Starts a zip with a single producer, whose size all further producers must have.
+    pub fn new(producer: P) -> Self {
+        Self {
+            size: producer.size(),
+            parts: (producer,),
+        }
+    }
+}
+
+impl<P, const D: usize> Zip<P, D> {
+    /// The size shared by all producers.
+    pub fn size(&self) -> [usize; D] {
+        self.size
+    }
+}
+
+macro_rules! impl_zip_and {
+    ($($p:ident $i:ident),+) => {
+        impl<$($p,)+ const D: usize> Zip<($($p,)+), D> {
+            /// Adds another producer. Panics if its size is not the size of the others.
+            pub fn and<Q: Producer<D>>(self, producer: Q) -> Zip<($($p,)+ Q,), D> {
+                self.try_and(producer).unwrap_or_else(|e| panic!("{e}"))
+            }
+
+            /// Like [`and`](Self::and), but returns the first axis with a different size
+            /// instead of panicking.
+            pub fn try_and<Q: Producer<D>>(
+                self,
+                producer: Q,
+            ) -> Result<Zip<($($p,)+ Q,), D>, Error> {
+                check_size(self.size, producer.size())?;
+                let ($($i,)+) = self.parts;
+                Ok(Zip {
+                    parts: ($($i,)+ producer,),
+                    size: self.size,
+                })
+            }
+        }
+    };
+}
+
+impl_zip_and!(P1 p1);
+impl_zip_and!(P1 p1, P2 p2);
+impl_zip_and!(P1 p1, P2 p2, P3 p3);
+impl_zip_and!(P1 p1, P2 p2, P3 p3, P4 p4);
+impl_zip_and!(P1 p1, P2 p2, P3 p3, P4 p4, P5 p5);
+
+macro_rules! impl_zip_iter {
+    ($($p:ident $i:ident $x:ident),+) => {
+        impl<$($p: Producer<D>,)+ const D: usize> Zip<($($p,)+), D> {
+            /// Calls `f` with the location of every element and the references into all
+            /// producers.
+            #[allow(clippy::too_many_arguments)]
+            pub fn for_each<F: FnMut([usize; D], $($p::Elem),+)>(self, mut f: F) {
+                self.into_iter().for_each(|(loc, $($i),+)| f(loc, $($i),+))
+            }
+        }
+
+        impl<$($p: Producer<D>,)+ const D: usize> IntoIterator for Zip<($($p,)+), D> {
+            type Item = ([usize; D], $($p::Elem),+);
+            type IntoIter = ZipIter<($($p::IntoIter,)+), D>;
+
+            fn into_iter(self) -> Self::IntoIter {
+                let ($($i,)+) = self.parts;
+                ZipIter {
+                    iters: ($($i.into_iter(),)+),
+                }
+            }
+        }
+
+        impl<$($p: Iterator<Item = ([usize; D], $x)>, $x,)+ const D: usize> Iterator
+            for ZipIter<($($p,)+), D>
+        {
+            type Item = ([usize; D], $($x),+);
+
+            #[inline]
+            fn next(&mut self) -> Option<Self::Item> {
+                let ($($i,)+) = &mut self.iters;
+                $(let $i = $i.next()?;)+
+                // all producers have the same size, so their locations are the same
+                let locs = [$($i.0),+];
+                Some((locs[0], $($i.1),+))
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                let ($($i,)+) = &self.iters;
+                let mut hint = (usize::MAX, None::<usize>);
+                $(let (lower, upper) = $i.size_hint();
+                hint.0 = hint.0.min(lower);
+                hint.1 = match (hint.1, upper) {
+                    (Some(a), Some(b)) => Some(a.min(b)),
+                    (a, b) => a.or(b),
+                };)+
+                hint
+            }
+        }
+
+        impl<$($p: ExactSizeIterator<Item = ([usize; D], $x)>, $x,)+ const D: usize>
+            ExactSizeIterator for ZipIter<($($p,)+), D>
+        {
+        }
+    };
+}
+
+impl_zip_iter!(P1 p1 X1);
+impl_zip_iter!(P1 p1 X1, P2 p2 X2);
+impl_zip_iter!(P1 p1 X1, P2 p2 X2, P3 p3 X3);
+impl_zip_iter!(P1 p1 X1, P2 p2 X2, P3 p3 X3, P4 p4 X4);
+impl_zip_iter!(P1 p1 X1, P2 p2 X2, P3 p3 X3, P4 p4 X4, P5 p5 X5);
+impl_zip_iter!(P1 p1 X1, P2 p2 X2, P3 p3 X3, P4 p4 X4, P5 p5 X5, P6 p6 X6);
+
+impl<'a, T, const D: usize> ArrayView<'a, T, D> {
+    /// Applies `f` to every element, returning a new array in standard layout.
+    pub fn map<U, F: FnMut(&'a T) -> U>(&self, mut f: F) -> Array<U, D> {
+        self.mapv_with_index(|_, x| f(x))
+    }
+
+    /// Like [`map`](Self::map), but also passes the location of every element to `f`.
+    pub fn mapv_with_index<U, F: FnMut([usize; D], &'a T) -> U>(&self, mut f: F) -> Array<U, D> {
+        let data = self.iter().map(|(loc, x)| f(loc, x)).collect();
+        Array::from_parts(self.size, standard_stride(self.size).unwrap().0, data)
+    }
+
+    /// Combines every element with the element of `other` at the same location, returning a
+    /// new array in standard layout. Panics if the sizes differ.
+    pub fn zip_map<P: Producer<D>, U, F: FnMut(&'a T, P::Elem) -> U>(
+        &self,
+        other: P,
+        mut f: F,
+    ) -> Array<U, D> {
+        let data = Zip::new(*self)
+            .and(other)
+            .into_iter()
+            .map(|(_, x, y)| f(x, y))
+            .collect();
+        Array::from_parts(self.size, standard_stride(self.size).unwrap().0, data)
+    }
+}
+
+impl<T, const D: usize> ArrayViewMut<'_, T, D> {
+    /// Replaces every element by applying `f` to it.
+    pub fn map_inplace<F: FnMut(&mut T)>(&mut self, mut f: F) {
+        self.iter_mut().for_each(|(_, x)| f(x));
+    }
+}
+
+impl<T, const D: usize, A: Allocator> Array<T, D, A> {
+    /// Applies `f` to every element, see [`ArrayView::map`].
+    pub fn map<'a, U, F: FnMut(&'a T) -> U>(&'a self, f: F) -> Array<U, D> {
+        self.view().map(f)
+    }
+
+    /// Applies `f` to every element and its location, see [`ArrayView::mapv_with_index`].
+    pub fn mapv_with_index<'a, U, F: FnMut([usize; D], &'a T) -> U>(&'a self, f: F) -> Array<U, D> {
+        self.view().mapv_with_index(f)
+    }
+
+    /// Combines every element with the element of `other` at the same location, see
+    /// [`ArrayView::zip_map`].
+    pub fn zip_map<'a, P: Producer<D>, U, F: FnMut(&'a T, P::Elem) -> U>(
+        &'a self,
+        other: P,
+        f: F,
+    ) -> Array<U, D> {
+        self.view().zip_map(other, f)
+    }
+
+    /// Replaces every element by applying `f` to it. Unlike the other map functions, this
+    /// keeps the memory layout of the array.
+    pub fn map_inplace<F: FnMut(&mut T)>(&mut self, f: F) {
+        self.data.iter_mut().for_each(f);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Array, Error, Zip};
+
+    #[test]
+    fn map() {
+        let array = Array::new_by_enumeration([3, 2], |i| i).transpose();
+        let doubled = array.map(|x| x * 2);
+        assert_eq!(doubled.size(), [2, 3]);
+        assert_eq!(doubled[[1, 2]], 10);
+        assert!(doubled.is_standard_layout());
+        let indexed = array.mapv_with_index(|[x, y], v| x * 10 + y * 100 + v);
+        assert_eq!(indexed[[1, 2]], 215);
+
+        let mut array = array;
+        array.map_inplace(|x| *x += 1);
+        assert_eq!(array[[1, 2]], 6);
+        array.view_mut().map_inplace(|x| *x *= 2);
+        assert_eq!(array[[1, 2]], 12);
+
+        let sums = array.zip_map(&doubled, |a, b| a + b);
+        assert_eq!(sums[[1, 2]], 22);
+    }
+
+    #[test]
+    fn zip() {
+        let a = Array::new_by_enumeration([2, 2], |i| i);
+        let b = Array::new_with([2, 2], 1);
+        let c = Array::new_with([2, 2], 2);
+        let d = Array::new_with([2, 2], 3);
+        let e = Array::new_with([2, 2], 4);
+        let mut out = Array::new_with([2, 2], 0);
+        let zip = Zip::new(&mut out).and(&a).and(&b).and(&c).and(&d).and(&e);
+        assert_eq!(zip.size(), [2, 2]);
+        assert_eq!(zip.into_iter().len(), 4);
+        Zip::new(&mut out)
+            .and(a.view())
+            .and(&b)
+            .and(&c)
+            .and(&d)
+            .and(&e)
+            .for_each(|_, o, a, b, c, d, e| *o = a + b + c + d + e);
+        assert_eq!(out.as_flattened(), &[10, 11, 12, 13]);
+
+        let locs = Zip::new(&a)
+            .and(b.slice(crate::s![.., ..]))
+            .into_iter()
+            .map(|(loc, _, _)| loc)
+            .collect::<Vec<_>>();
+        assert_eq!(locs, vec![[0, 0], [1, 0], [0, 1], [1, 1]]);
+
+        assert_eq!(
+            Zip::new(&a).try_and(&Array::new_with([2, 3], 0)).err(),
+            Some(Error::IncompatibleShapes {
+                axis: 1,
+                left: 2,
+                right: 3
+            })
+        );
+    }
+}